@@ -0,0 +1,130 @@
+/// A Linux capability.
+///
+/// Read [capabilities(7)](man:capabilities(7)) for details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Capability {
+    /// `CAP_CHOWN`.
+    Chown = 0,
+    /// `CAP_DAC_OVERRIDE`.
+    DacOverride = 1,
+    /// `CAP_DAC_READ_SEARCH`.
+    DacReadSearch = 2,
+    /// `CAP_FOWNER`.
+    Fowner = 3,
+    /// `CAP_FSETID`.
+    Fsetid = 4,
+    /// `CAP_KILL`.
+    Kill = 5,
+    /// `CAP_SETGID`.
+    Setgid = 6,
+    /// `CAP_SETUID`.
+    Setuid = 7,
+    /// `CAP_SETPCAP`.
+    Setpcap = 8,
+    /// `CAP_LINUX_IMMUTABLE`.
+    LinuxImmutable = 9,
+    /// `CAP_NET_BIND_SERVICE`.
+    NetBindService = 10,
+    /// `CAP_NET_BROADCAST`.
+    NetBroadcast = 11,
+    /// `CAP_NET_ADMIN`.
+    NetAdmin = 12,
+    /// `CAP_NET_RAW`.
+    NetRaw = 13,
+    /// `CAP_IPC_LOCK`.
+    IpcLock = 14,
+    /// `CAP_IPC_OWNER`.
+    IpcOwner = 15,
+    /// `CAP_SYS_MODULE`.
+    SysModule = 16,
+    /// `CAP_SYS_RAWIO`.
+    SysRawio = 17,
+    /// `CAP_SYS_CHROOT`.
+    SysChroot = 18,
+    /// `CAP_SYS_PTRACE`.
+    SysPtrace = 19,
+    /// `CAP_SYS_PACCT`.
+    SysPacct = 20,
+    /// `CAP_SYS_ADMIN`.
+    SysAdmin = 21,
+    /// `CAP_SYS_BOOT`.
+    SysBoot = 22,
+    /// `CAP_SYS_NICE`.
+    SysNice = 23,
+    /// `CAP_SYS_RESOURCE`.
+    SysResource = 24,
+    /// `CAP_SYS_TIME`.
+    SysTime = 25,
+    /// `CAP_SYS_TTY_CONFIG`.
+    SysTtyConfig = 26,
+    /// `CAP_MKNOD`.
+    Mknod = 27,
+    /// `CAP_LEASE`.
+    Lease = 28,
+    /// `CAP_AUDIT_WRITE`.
+    AuditWrite = 29,
+    /// `CAP_AUDIT_CONTROL`.
+    AuditControl = 30,
+    /// `CAP_SETFCAP`.
+    Setfcap = 31,
+    /// `CAP_MAC_OVERRIDE`.
+    MacOverride = 32,
+    /// `CAP_MAC_ADMIN`.
+    MacAdmin = 33,
+    /// `CAP_SYSLOG`.
+    Syslog = 34,
+    /// `CAP_WAKE_ALARM`.
+    WakeAlarm = 35,
+    /// `CAP_BLOCK_SUSPEND`.
+    BlockSuspend = 36,
+    /// `CAP_AUDIT_READ`.
+    AuditRead = 37,
+    /// `CAP_PERFMON`, since Linux 5.8.
+    Perfmon = 38,
+    /// `CAP_BPF`, since Linux 5.8.
+    Bpf = 39,
+    /// `CAP_CHECKPOINT_RESTORE`, since Linux 5.9.
+    CheckpointRestore = 40,
+}
+
+// The capability sets are u64 bit masks in the D-Bus API.  The bits
+// unknown by the kernel are ignored by systemd, so the complement of a
+// set is simply the bitwise not.
+#[cfg(feature = "systemd_236")]
+pub fn mask<I: IntoIterator<Item = Capability>>(caps: I, deny: bool) -> u64 {
+    let x = caps.into_iter().fold(0u64, |x, c| x | 1 << c as u32);
+    if deny {
+        !x
+    } else {
+        x
+    }
+}
+
+/// A flag of the secure bits, controlling how the capabilities are
+/// handled for UID 0.
+///
+/// Read `SecureBits=` in [systemd.exec(5)](man:systemd.exec(5)) and
+/// [capabilities(7)](man:capabilities(7)) for details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SecureBit {
+    /// `SECBIT_NOROOT`, don't grant capabilities when UID 0 executes a
+    /// program.
+    NoRoot = 0,
+    /// `SECBIT_NOROOT_LOCKED`, lock [SecureBit::NoRoot].
+    NoRootLocked = 1,
+    /// `SECBIT_NO_SETUID_FIXUP`, don't adjust the capabilities when the
+    /// UIDs change between 0 and nonzero.
+    NoSetuidFixup = 2,
+    /// `SECBIT_NO_SETUID_FIXUP_LOCKED`, lock [SecureBit::NoSetuidFixup].
+    NoSetuidFixupLocked = 3,
+    /// `SECBIT_KEEP_CAPS`, keep the permitted capabilities when all the
+    /// UIDs change from 0 to nonzero.
+    KeepCaps = 4,
+    /// `SECBIT_KEEP_CAPS_LOCKED`, lock [SecureBit::KeepCaps].
+    KeepCapsLocked = 5,
+}
+
+#[cfg(feature = "systemd_236")]
+pub fn secure_bits<I: IntoIterator<Item = SecureBit>>(bits: I) -> i32 {
+    bits.into_iter().fold(0, |x, b| x | 1 << b as u32)
+}
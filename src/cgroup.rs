@@ -0,0 +1,77 @@
+use byte_unit::Byte;
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+// The statistics recorded by StartedRun::sample for FinishedRun, as the
+// control group is removed by systemd once the unit is stopped.  All of
+// them are counters or peaks, so merging two samples keeps the larger
+// value.  Read "Core Interface Files" and "Controllers" in
+// https://docs.kernel.org/admin-guide/cgroup-v2.html for details.
+#[derive(Debug, Default)]
+pub struct Stat {
+    pub cpu_user: Option<Duration>,
+    pub cpu_system: Option<Duration>,
+    pub memory_peak: Option<Byte>,
+    pub memory_swap_peak: Option<Byte>,
+    pub oom_kills: Option<u64>,
+    pub tasks_peak: Option<u64>,
+    pub io_read_bytes: Option<Byte>,
+    pub io_write_bytes: Option<Byte>,
+}
+
+impl Stat {
+    // Merge a later sample into self.  A value missing from the later
+    // one, for example because the unit has just been stopped, does not
+    // discard the earlier one.
+    pub fn merge(&mut self, other: Self) {
+        self.cpu_user = self.cpu_user.max(other.cpu_user);
+        self.cpu_system = self.cpu_system.max(other.cpu_system);
+        self.memory_peak = self.memory_peak.max(other.memory_peak);
+        self.memory_swap_peak = self.memory_swap_peak.max(other.memory_swap_peak);
+        self.oom_kills = self.oom_kills.max(other.oom_kills);
+        self.tasks_peak = self.tasks_peak.max(other.tasks_peak);
+        self.io_read_bytes = self.io_read_bytes.max(other.io_read_bytes);
+        self.io_write_bytes = self.io_write_bytes.max(other.io_write_bytes);
+    }
+}
+
+// Read the statistics in the control group `path`, which is the
+// ControlGroup property of the unit.  The files missing (for example, if
+// the unified control group is not used) are skipped.
+pub fn read(path: &str) -> Stat {
+    let dir = Path::new(CGROUP_ROOT).join(path.trim_start_matches('/'));
+    let cpu = read_keyed(&dir.join("cpu.stat"));
+    let usec = |k| cpu.get(k).copied().map(Duration::from_micros);
+    let events = read_keyed(&dir.join("memory.events"));
+    Stat {
+        cpu_user: usec("user_usec"),
+        cpu_system: usec("system_usec"),
+        memory_peak: read_u64(&dir.join("memory.peak")).map(Byte::from),
+        memory_swap_peak: read_u64(&dir.join("memory.swap.peak")).map(Byte::from),
+        oom_kills: events.get("oom_kill").copied(),
+        tasks_peak: read_u64(&dir.join("pids.peak")),
+        io_read_bytes: None,
+        io_write_bytes: None,
+    }
+}
+
+// Parse a single value file, like memory.peak.
+fn read_u64(path: &Path) -> Option<u64> {
+    std::fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+// Parse a flat keyed file, like cpu.stat.  An unreadable file is treated
+// as empty.
+fn read_keyed(path: &Path) -> HashMap<String, u64> {
+    std::fs::read_to_string(path)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|l| {
+            let (k, v) = l.split_once(' ')?;
+            Some((k.to_owned(), v.parse().ok()?))
+        })
+        .collect()
+}
@@ -11,19 +11,22 @@ pub enum CpuSchedulingPolicy {
 
 /// The CPU scheduling for running a transient service on the system service
 /// manager.
-/// See `CPUSchedulingPolicy=`, `CPUSchedulingPriority=`, and
-/// `CPUSchedulingResetOnFork=` in [systemd.exec(5)](man:systemd.exec(5))
-/// and [sched_setscheduler(2)](man:sched_setscheduler(2)) for details.
+/// See `CPUSchedulingPolicy=`, `CPUSchedulingPriority=`,
+/// `CPUSchedulingResetOnFork=`, and `Nice=` in
+/// [systemd.exec(5)](man:systemd.exec(5)) and
+/// [sched_setscheduler(2)](man:sched_setscheduler(2)) for details.
 pub struct CpuScheduling {
     policy: CpuSchedulingPolicy,
     real_time_priority: Option<u8>,
     reset_on_fork: bool,
+    nice: Option<i8>,
 }
 
-pub fn marshal(sched: CpuScheduling) -> (i32, Option<i32>, bool) {
+pub fn marshal(sched: CpuScheduling) -> (i32, Option<i32>, bool, Option<i32>) {
     let a = sched.policy as i32;
     let b = sched.real_time_priority.map(u8::into);
-    (a, b, sched.reset_on_fork)
+    let d = sched.nice.map(i8::into);
+    (a, b, sched.reset_on_fork, d)
 }
 
 impl Default for CpuScheduling {
@@ -33,6 +36,7 @@ impl Default for CpuScheduling {
             policy: CpuSchedulingPolicy::Other,
             real_time_priority: None,
             reset_on_fork: false,
+            nice: None,
         }
     }
 }
@@ -66,7 +70,7 @@ impl CpuScheduling {
         Self {
             policy: CpuSchedulingPolicy::Fifo,
             real_time_priority: Some(p.into()),
-            reset_on_fork: false,
+            ..Self::default()
         }
     }
 
@@ -89,7 +93,7 @@ impl CpuScheduling {
         Self {
             policy: CpuSchedulingPolicy::RoundRobin,
             real_time_priority: Some(p.into()),
-            reset_on_fork: false,
+            ..Self::default()
         }
     }
 
@@ -109,4 +113,20 @@ impl CpuScheduling {
             ..self
         }
     }
+
+    /// Set the nice level of the executed processes.  The nice level must
+    /// be in [-20, 19], or [None] will be returned.  A lower nice level
+    /// means a higher priority.
+    ///
+    /// It only affects the non-real-time policies, i. e. `SCHED_OTHER`
+    /// and `SCHED_BATCH`.  Read [sched(7)](man:sched(7)) for details.
+    pub fn nice(self, nice: i8) -> Option<Self> {
+        match nice {
+            -20..=19 => Some(Self {
+                nice: Some(nice),
+                ..self
+            }),
+            _ => None,
+        }
+    }
 }
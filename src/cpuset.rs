@@ -0,0 +1,217 @@
+use crate::{Error, Result};
+use std::collections::BTreeSet;
+use std::fmt;
+use std::str::FromStr;
+
+/// A set of CPUs or NUMA nodes, identified by their indices.
+///
+/// It can be parsed from the list format used by the kernel and systemd,
+/// for example `"0-3,8"` for the CPUs 0, 1, 2, 3, and 8.  It's also
+/// formatted into the list format with [Display](std::fmt::Display).
+///
+/// Read `CPUAffinity=` in [systemd.exec(5)](man:systemd.exec(5)) and
+/// "List format" in [cpuset(7)](man:cpuset(7)) for details.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CpuSet(BTreeSet<usize>);
+
+impl CpuSet {
+    /// The upper bound (exclusive) of the indices, same as the service
+    /// manager.
+    pub const MAX: usize = 8192;
+
+    /// Create an empty [CpuSet].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a CPU into the set.  If `cpu` is not less than [Self::MAX],
+    /// the set is not changed and false is returned.
+    pub fn insert(&mut self, cpu: usize) -> bool {
+        if cpu >= Self::MAX {
+            return false;
+        }
+        self.0.insert(cpu);
+        true
+    }
+
+    /// Check if a CPU is in the set.
+    pub fn contains(&self, cpu: usize) -> bool {
+        self.0.contains(&cpu)
+    }
+
+    /// Check if the set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Iterate the CPUs in the set, in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = &usize> {
+        self.0.iter()
+    }
+}
+
+impl FromIterator<usize> for CpuSet {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl<'a> IntoIterator for &'a CpuSet {
+    type Item = &'a usize;
+    type IntoIter = std::collections::btree_set::Iter<'a, usize>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl FromStr for CpuSet {
+    type Err = Error;
+
+    /// Parse the list format.  Both `,` and whitespaces are accepted as
+    /// the separator, like systemd.  The indices must be less than
+    /// [CpuSet::MAX].
+    fn from_str(s: &str) -> Result<Self> {
+        let bad = || Error::InvalidCpuSet(s.to_owned());
+        let mut set = Self::new();
+        for item in s.split([',', ' ', '\t', '\n']).filter(|x| !x.is_empty()) {
+            let (lo, hi) = item.split_once('-').unwrap_or((item, item));
+            let lo: usize = lo.parse().map_err(|_| bad())?;
+            let hi: usize = hi.parse().map_err(|_| bad())?;
+            if lo > hi || hi >= Self::MAX {
+                return Err(bad());
+            }
+            set.0.extend(lo..=hi);
+        }
+        Ok(set)
+    }
+}
+
+impl fmt::Display for CpuSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut first = true;
+        let mut it = self.0.iter().copied().peekable();
+        while let Some(lo) = it.next() {
+            let mut hi = lo;
+            while it.peek() == Some(&(hi + 1)) {
+                hi = it.next().unwrap();
+            }
+            if !first {
+                write!(f, ",")?;
+            }
+            first = false;
+            match hi - lo {
+                0 => write!(f, "{}", lo)?,
+                _ => write!(f, "{}-{}", lo, hi)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+// The bit mask format used by systemd D-Bus API, the CPU i is the bit
+// (i % 8) of the byte (i / 8).
+pub fn mask(set: &CpuSet) -> Vec<u8> {
+    let mut mask = vec![];
+    for &cpu in set.0.range(..CpuSet::MAX) {
+        let (x, y) = (cpu / 8, cpu % 8);
+        if mask.len() <= x {
+            mask.resize(x + 1, 0u8);
+        }
+        mask[x] |= 1 << y;
+    }
+    mask
+}
+
+// Check if all the indices in the set are less than CpuSet::MAX, as
+// they are not checked when collecting a CpuSet from an iterator.
+pub fn check_range(set: &CpuSet) -> Result<()> {
+    match set.0.range(CpuSet::MAX..).next() {
+        Some(x) => Err(Error::InvalidCpuSet(x.to_string())),
+        None => Ok(()),
+    }
+}
+
+// Check if all the CPUs in the set are online on this host.  See
+// check_nodes_online.
+pub fn check_online(set: &CpuSet) -> Result<()> {
+    match find_offline(set, "cpu") {
+        Some(x) => Err(Error::CpuOffline(x)),
+        None => Ok(()),
+    }
+}
+
+// Check if all the NUMA nodes in the set are online on this host.  Like
+// check_online, this reads /sys of the local host, so the caller should
+// skip it if the service manager may be on another host or in a
+// container.
+pub fn check_nodes_online(set: &CpuSet) -> Result<()> {
+    match find_offline(set, "node") {
+        Some(x) => Err(Error::NumaNodeOffline(x)),
+        None => Ok(()),
+    }
+}
+
+// If the online list can't be read (for example, /sys is not mounted, or
+// the kernel does not support NUMA), leave the check to the service
+// manager.
+fn find_offline(set: &CpuSet, kind: &str) -> Option<usize> {
+    let path = format!("/sys/devices/system/{}/online", kind);
+    let online = std::fs::read_to_string(path).ok()?.parse::<CpuSet>().ok()?;
+    set.iter().copied().find(|&x| !online.contains(x))
+}
+
+/// The NUMA memory policy of the executed processes.  The [CpuSet] here
+/// is the set of NUMA nodes, instead of CPUs.
+///
+/// Read `NUMAPolicy=` and `NUMAMask=` in
+/// [systemd.exec(5)](man:systemd.exec(5)) and
+/// [set_mempolicy(2)](man:set_mempolicy(2)) for details.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NumaPolicy {
+    /// `MPOL_DEFAULT`, use the policy of the service manager.
+    Default,
+    /// `MPOL_PREFERRED`, allocate memory from the preferred node if
+    /// possible.  Only the first node in the set is used.
+    Preferred(CpuSet),
+    /// `MPOL_BIND`, allocate memory from the nodes only.  The set must
+    /// not be empty.
+    Bind(CpuSet),
+    /// `MPOL_INTERLEAVE`, interleave memory allocations across the nodes.
+    /// The set must not be empty.
+    Interleave(CpuSet),
+    /// `MPOL_LOCAL`, allocate memory from the node of the CPU running the
+    /// allocation.
+    Local,
+}
+
+pub fn numa_nodes(p: &NumaPolicy) -> Option<&CpuSet> {
+    use NumaPolicy::*;
+    match p {
+        Preferred(x) | Bind(x) | Interleave(x) => Some(x),
+        Default | Local => None,
+    }
+}
+
+// Check if the NUMA policy has a node to use.  The kernel rejects an
+// empty set for MPOL_BIND and MPOL_INTERLEAVE, failing the service at
+// exec time.
+pub fn check_numa_policy(p: &NumaPolicy) -> Result<()> {
+    match p {
+        NumaPolicy::Bind(x) | NumaPolicy::Interleave(x) if x.is_empty() => {
+            Err(Error::InvalidCpuSet(String::new()))
+        }
+        _ => Ok(()),
+    }
+}
+
+pub fn marshal_numa(p: NumaPolicy) -> (i32, Option<Vec<u8>>) {
+    use NumaPolicy::*;
+    match p {
+        Default => (0, None),
+        Preferred(x) => (1, Some(mask(&x))),
+        Bind(x) => (2, Some(mask(&x))),
+        Interleave(x) => (3, Some(mask(&x))),
+        Local => (4, None),
+    }
+}
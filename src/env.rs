@@ -0,0 +1,79 @@
+use crate::{Error, Result};
+use zbus::zvariant::Value;
+
+/// The environment of a transient service.
+#[derive(Default)]
+pub struct Environment {
+    vars: Vec<(String, String)>,
+    unset: Vec<String>,
+    files: Vec<String>,
+    pass: Vec<String>,
+}
+
+#[allow(dead_code)]
+impl Environment {
+    pub fn set(&mut self, k: &str, v: &str) {
+        self.vars.retain(|(x, _)| x != k);
+        self.unset.retain(|x| x != k);
+        self.vars.push((k.to_owned(), v.to_owned()));
+    }
+
+    pub fn remove(&mut self, k: &str) {
+        self.vars.retain(|(x, _)| x != k);
+        if !self.unset.iter().any(|x| x == k) {
+            self.unset.push(k.to_owned());
+        }
+    }
+
+    pub fn add_file(&mut self, path: &str) {
+        self.files.push(path.to_owned());
+    }
+
+    pub fn pass(&mut self, k: &str) {
+        if !self.pass.iter().any(|x| x == k) {
+            self.pass.push(k.to_owned());
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        let keys = self.vars.iter().map(|(k, _)| k);
+        for k in keys.chain(&self.unset).chain(&self.pass) {
+            if k.is_empty() || k.contains(['=', '\0']) {
+                return Err(Error::InvalidEnvironment(k.clone()));
+            }
+        }
+        for (k, v) in &self.vars {
+            if v.contains('\0') {
+                return Err(Error::InvalidEnvironment(k.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn marshal(self) -> Vec<(&'static str, Value<'static>)> {
+        let mut r = vec![];
+        if !self.vars.is_empty() {
+            let v = self
+                .vars
+                .into_iter()
+                .map(|(k, v)| k + "=" + &v)
+                .collect::<Vec<_>>();
+            r.push(("Environment", Value::from(v)));
+        }
+        if !self.files.is_empty() {
+            let v = self
+                .files
+                .into_iter()
+                .map(|x| (x, false))
+                .collect::<Vec<_>>();
+            r.push(("EnvironmentFiles", Value::from(v)));
+        }
+        if !self.unset.is_empty() {
+            r.push(("UnsetEnvironment", Value::from(self.unset)));
+        }
+        if !self.pass.is_empty() {
+            r.push(("PassEnvironment", Value::from(self.pass)));
+        }
+        r
+    }
+}
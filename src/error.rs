@@ -21,12 +21,60 @@ pub enum Error {
     /// An error quering one property of a unit.
     #[error("cannot query the property: {0}")]
     QueryPropertyFail(zbus::fdo::Error),
+    /// An invalid environment variable, containing `=` or NUL in the name
+    /// or NUL in the value, or with an empty name.
+    #[error("invalid environment variable: {0:?}")]
+    InvalidEnvironment(String),
+    /// An invalid field name for
+    /// [RunSystem::log_extra_fields](crate::RunSystem::log_extra_fields).
+    #[error("invalid log field name: {0:?}")]
+    InvalidLogField(String),
+    /// An invalid system call or group name for
+    /// [SystemCallFilter](crate::SystemCallFilter).
+    #[error("invalid system call name: {0:?}")]
+    InvalidSystemCall(String),
+    /// A path which is not absolute for
+    /// [AccessPath](crate::AccessPath).
+    #[error("path is not absolute: {0:?}")]
+    InvalidPath(String),
+    /// An invalid list of CPUs or NUMA nodes for
+    /// [CpuSet](crate::CpuSet).
+    #[error("invalid CPU list: {0:?}")]
+    InvalidCpuSet(String),
+    /// A CPU which is not online on the host is specified.
+    #[error("CPU {0} is not online")]
+    CpuOffline(usize),
+    /// A NUMA node which is not online on the host is specified.
+    #[error("NUMA node {0} is not online")]
+    NumaNodeOffline(usize),
+    /// An error creating a pipe for [InputSpec::pipe](crate::InputSpec::pipe)
+    /// or [OutputSpec::pipe](crate::OutputSpec::pipe).
+    #[error("cannot create a pipe: {0}")]
+    CreatePipeFail(std::io::Error),
+    /// An error allocating a pseudo-terminal for
+    /// [RunSystem::pty](crate::RunSystem::pty).
+    #[error("cannot allocate a pseudo-terminal: {0}")]
+    CreatePtyFail(std::io::Error),
     /// An error calling systemd to start the transient unit.
     #[error("cannot start the transient service: {0}")]
     StartFail(zbus::Error),
+    /// An error calling systemd to stop the transient unit.
+    #[error("cannot stop the transient service: {0}")]
+    StopFail(zbus::Error),
+    /// An error calling systemd to kill the processes of the transient
+    /// unit.
+    #[error("cannot kill the transient service: {0}")]
+    KillFail(zbus::Error),
     /// An error attempting to calculate the time usage of a service.
     #[error("cannot calculate {0} time usage: t0 = {1:?}, t1 = {2:?}")]
     TimeUsageFail(&'static str, Box<OwnedValue>, Box<OwnedValue>),
+    /// The usage of some resource is not available from the service
+    /// manager, most likely because the accounting is not supported.
+    #[error("{0} usage is unavailable")]
+    UsageUnavailable(&'static str),
+    /// An error reading the journal with `journalctl`.
+    #[error("cannot read the journal: {0}")]
+    ReadJournalFail(std::io::Error),
 }
 
 /// Alias for a [Result][std::result::Result] with the error type [Error].
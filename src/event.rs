@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+use zbus::zvariant::Value;
+
+/// The active state of a unit.
+///
+/// Read `ActiveState` in
+/// [org.freedesktop.systemd1(5)](man:org.freedesktop.systemd1(5)) for
+/// details.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ActiveState {
+    /// The unit is active.
+    Active,
+    /// The unit is active and currently reloading its configuration.
+    Reloading,
+    /// The unit is inactive and the previous run was successful, or no
+    /// previous run has taken place yet.
+    Inactive,
+    /// The unit is inactive and the previous run was not successful.
+    Failed,
+    /// The unit has previously been inactive but is currently in the
+    /// process of entering an active state.
+    Activating,
+    /// The unit is currently in the process of deactivation.
+    Deactivating,
+    /// The unit is inactive and a maintenance operation is in progress.
+    Maintenance,
+    /// The unit is active and a new mount is being activated in its
+    /// namespace.
+    Refreshing,
+    /// A state not known by this crate.
+    Other(String),
+}
+
+/// An event happened to a [StartedRun](crate::StartedRun), produced by
+/// [StartedRun::events](crate::StartedRun::events).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UnitEvent {
+    /// A job with the ID is queued for the unit.
+    JobQueued(u32),
+    /// The job of the unit is finished and removed.
+    JobRemoved,
+    /// The active state of the unit is changed.
+    ActiveState(ActiveState),
+    /// The sub state of the unit is changed.  The possible values depend
+    /// on the unit type, for example `running` or `exited`.
+    SubState(String),
+    /// The PID of the main process of the unit is known.
+    MainPid(u32),
+}
+
+pub fn parse_active_state(s: &str) -> ActiveState {
+    use ActiveState::*;
+    match s {
+        "active" => Active,
+        "reloading" => Reloading,
+        "inactive" => Inactive,
+        "failed" => Failed,
+        "activating" => Activating,
+        "deactivating" => Deactivating,
+        "maintenance" => Maintenance,
+        "refreshing" => Refreshing,
+        _ => Other(s.to_owned()),
+    }
+}
+
+pub fn parse(changed: &HashMap<&str, Value<'_>>) -> Vec<UnitEvent> {
+    let mut events = vec![];
+
+    if let Some(Value::Structure(job)) = changed.get("Job") {
+        match job.fields().first() {
+            Some(Value::U32(0)) => events.push(UnitEvent::JobRemoved),
+            Some(Value::U32(id)) => events.push(UnitEvent::JobQueued(*id)),
+            _ => {}
+        }
+    }
+
+    if let Some(Value::Str(state)) = changed.get("ActiveState") {
+        let state = parse_active_state(state.as_str());
+        events.push(UnitEvent::ActiveState(state));
+    }
+
+    if let Some(Value::Str(state)) = changed.get("SubState") {
+        events.push(UnitEvent::SubState(state.as_str().to_owned()));
+    }
+
+    match changed.get("MainPID") {
+        Some(Value::U32(0)) | None => {}
+        Some(Value::U32(pid)) => events.push(UnitEvent::MainPid(*pid)),
+        _ => {}
+    }
+
+    events
+}
@@ -57,7 +57,7 @@ pub fn is_session(i: &Identity) -> bool {
     matches!(i, Identity(IdentityInner::Session))
 }
 
-pub fn unit_properties(i: &Identity) -> Vec<(&'static str, Value)> {
+pub fn unit_properties(i: &Identity) -> Vec<(&'static str, Value<'_>)> {
     match i {
         Identity(IdentityInner::Session) => vec![],
         Identity(IdentityInner::UserGroup(u, g)) => vec![
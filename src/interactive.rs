@@ -0,0 +1,95 @@
+use crate::{default_unit_name, identity, ioredirect, manager};
+use crate::{Error, FinishedRun, InputSpec, Manager, OutputSpec, Result, RunSystem, RunUser};
+
+/// Two transient services talking to each other, as the solution and the
+/// interactor of an interactive problem.
+///
+/// The output of each transient service is connected to the input of the
+/// other one with a pipe, overriding [RunSystem::stdin] and
+/// [RunSystem::stdout].  The standard error is not touched.
+pub struct Interaction {
+    solution: RunSystem,
+    interactor: RunSystem,
+    share_namespace: bool,
+}
+
+impl Interaction {
+    /// Create a new [Interaction] from the [RunSystem] of the solution and
+    /// the [RunSystem] of the interactor.
+    pub fn new(solution: RunSystem, interactor: RunSystem) -> Self {
+        Self {
+            solution,
+            interactor,
+            share_namespace: false,
+        }
+    }
+
+    /// Like [Self::new], but for two [RunUser]s.
+    pub fn user(solution: RunUser, interactor: RunUser) -> Self {
+        Self::new(solution.0, interactor.0)
+    }
+
+    /// Make the interactor join the namespace of the solution, with
+    /// [RunSystem::joins_namespace_of].  The same restrictions apply,
+    /// i. e. this only has an effect if [RunSystem::private_network],
+    /// [RunSystem::private_ipc], and/or
+    /// [Identity::dynamic](crate::Identity::dynamic) is in effect for
+    /// both transient services.
+    pub fn share_namespace(self) -> Self {
+        Self {
+            share_namespace: true,
+            ..self
+        }
+    }
+
+    /// Start both transient services and wait until both are finished,
+    /// returning the [FinishedRun] of the solution and the interactor.
+    ///
+    /// A new D-Bus connection is opened like [RunSystem::start].  If
+    /// anything fails, the transient services already started are
+    /// stopped.
+    pub async fn run(self) -> Result<(FinishedRun, FinishedRun)> {
+        let manager = if identity::is_session(&self.solution.identity) {
+            Manager::user().await
+        } else {
+            Manager::system().await
+        }?;
+        self.run_with(&manager).await
+    }
+
+    /// Like [Self::run], but start the transient services on the
+    /// [Manager].
+    pub async fn run_with(self, manager: &Manager) -> Result<(FinishedRun, FinishedRun)> {
+        let local = manager::is_local(manager);
+        self.solution.validate(local)?;
+        self.interactor.validate(local)?;
+
+        let (sol_in, int_out) = ioredirect::pipe().map_err(Error::CreatePipeFail)?;
+        let (int_in, sol_out) = ioredirect::pipe().map_err(Error::CreatePipeFail)?;
+
+        let mut solution = self
+            .solution
+            .stdin(InputSpec::fd(sol_in))
+            .stdout(OutputSpec::fd(sol_out));
+        let mut interactor = self
+            .interactor
+            .stdin(InputSpec::fd(int_in))
+            .stdout(OutputSpec::fd(int_out));
+
+        if self.share_namespace {
+            if solution.service_name.is_none() {
+                let seq = manager::next_seq(manager);
+                solution.service_name = Some(default_unit_name(manager::bus(manager), seq)?);
+            }
+            let name = solution.service_name.clone().unwrap();
+            interactor = interactor.joins_namespace_of(name);
+        }
+
+        // Our copies of the pipe ends are closed once each transient
+        // service is started, so either transient service gets EOF or
+        // EPIPE once the other one exits.
+        let solution = solution.start_with(manager).await?.stop_on_drop();
+        let interactor = interactor.start_with(manager).await?.stop_on_drop();
+        futures::try_join!(solution.wait(), interactor.wait())
+    }
+}
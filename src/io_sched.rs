@@ -0,0 +1,62 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum IoSchedulingClass {
+    Realtime = 1,
+    BestEffort = 2,
+    Idle = 3,
+}
+
+/// The IO scheduling for running a transient service.
+/// See `IOSchedulingClass=` and `IOSchedulingPriority=` in
+/// [systemd.exec(5)](man:systemd.exec(5)) and
+/// [ioprio_set(2)](man:ioprio_set(2)) for details.
+///
+/// The IO scheduling class and priority are only honored by the IO
+/// schedulers supporting them, for example BFQ.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IoScheduling {
+    class: IoSchedulingClass,
+    priority: Option<u8>,
+}
+
+pub fn marshal(sched: IoScheduling) -> (i32, Option<i32>) {
+    (sched.class as i32, sched.priority.map(u8::into))
+}
+
+impl IoScheduling {
+    fn with_priority(class: IoSchedulingClass, p: u8) -> Option<Self> {
+        match p {
+            0..=7 => Some(Self {
+                class,
+                priority: Some(p),
+            }),
+            _ => None,
+        }
+    }
+
+    /// The real-time class, `IOPRIO_CLASS_RT`, with specified priority.
+    /// The priority must be in [0, 7], or [None] will be returned.  A
+    /// lower value means a higher priority.
+    ///
+    /// Using this class needs privileges, so it will fail with
+    /// [RunUser](crate::RunUser).
+    pub fn realtime(p: u8) -> Option<Self> {
+        Self::with_priority(IoSchedulingClass::Realtime, p)
+    }
+
+    /// The best-effort class, `IOPRIO_CLASS_BE`, with specified priority.
+    /// The priority must be in [0, 7], or [None] will be returned.  A
+    /// lower value means a higher priority.
+    pub fn best_effort(p: u8) -> Option<Self> {
+        Self::with_priority(IoSchedulingClass::BestEffort, p)
+    }
+
+    /// The idle class, `IOPRIO_CLASS_IDLE`, for running very low priority
+    /// background jobs.  The processes only get the disk time when no
+    /// other process needs it.
+    pub fn idle() -> Self {
+        Self {
+            class: IoSchedulingClass::Idle,
+            priority: None,
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use std::time::Duration;
+use zbus::zvariant::Value;
+
+/// The IO controller settings of a transient service.
+#[derive(Default)]
+pub struct IoControl {
+    weight: Option<u64>,
+    read_bandwidth_max: Vec<(String, u64)>,
+    write_bandwidth_max: Vec<(String, u64)>,
+    read_iops_max: Vec<(String, u64)>,
+    write_iops_max: Vec<(String, u64)>,
+    device_latency_target: Vec<(String, u64)>,
+}
+
+// Setting the same device again overrides the previous setting.
+fn set_device(v: &mut Vec<(String, u64)>, dev: &str, x: u64) {
+    v.retain(|(d, _)| d != dev);
+    v.push((dev.to_owned(), x));
+}
+
+#[allow(dead_code)]
+impl IoControl {
+    pub fn weight(&mut self, w: u64) {
+        self.weight = Some(w.clamp(1, 10000));
+    }
+
+    pub fn read_bandwidth_max(&mut self, dev: &str, bytes: u64) {
+        set_device(&mut self.read_bandwidth_max, dev, bytes);
+    }
+
+    pub fn write_bandwidth_max(&mut self, dev: &str, bytes: u64) {
+        set_device(&mut self.write_bandwidth_max, dev, bytes);
+    }
+
+    pub fn read_iops_max(&mut self, dev: &str, iops: u64) {
+        set_device(&mut self.read_iops_max, dev, iops);
+    }
+
+    pub fn write_iops_max(&mut self, dev: &str, iops: u64) {
+        set_device(&mut self.write_iops_max, dev, iops);
+    }
+
+    pub fn device_latency_target(&mut self, dev: &str, d: Duration) {
+        let usec = u64::try_from(d.as_micros()).unwrap_or(u64::MAX);
+        set_device(&mut self.device_latency_target, dev, usec);
+    }
+
+    pub fn marshal(self) -> Vec<(&'static str, Value<'static>)> {
+        let mut r = vec![];
+        if let Some(w) = self.weight {
+            r.push(("IOWeight", Value::from(w)));
+        }
+        for (k, v) in [
+            ("IOReadBandwidthMax", self.read_bandwidth_max),
+            ("IOWriteBandwidthMax", self.write_bandwidth_max),
+            ("IOReadIOPSMax", self.read_iops_max),
+            ("IOWriteIOPSMax", self.write_iops_max),
+            ("IODeviceLatencyTargetUSec", self.device_latency_target),
+        ] {
+            if !v.is_empty() {
+                r.push((k, Value::from(v)));
+            }
+        }
+        r
+    }
+}
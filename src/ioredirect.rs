@@ -1,3 +1,12 @@
+use async_io::Async;
+use futures::io::{AsyncRead, AsyncReadExt, AsyncWrite};
+use std::fs::File;
+use std::io;
+use std::os::fd::{FromRawFd, OwnedFd};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use zbus::zvariant::{Fd, Value};
+
 #[allow(dead_code)]
 pub enum Priv {
     Inherit,
@@ -6,6 +15,10 @@ pub enum Priv {
     File(String),
     Truncate(String),
     Append(String),
+    Pipe,
+    Data(Vec<u8>),
+    Fd(OwnedFd),
+    Tty,
 }
 
 impl Priv {
@@ -15,9 +28,13 @@ impl Priv {
             Inherit => ("", "inherit".to_string()),
             Null => ("", "null".to_string()),
             Journal => ("", "journal".to_string()),
+            Tty => ("", "tty".to_string()),
             File(x) => ("File", x),
             Truncate(x) => ("FileToTruncate", x),
             Append(x) => ("FileToAppend", x),
+            Pipe => unreachable!("pipe should be marshaled with the fd"),
+            Data(_) => unreachable!("data should be marshaled as bytes"),
+            Fd(_) => unreachable!("fd should be marshaled as is"),
         }
     }
 }
@@ -42,10 +59,97 @@ impl InputSpec {
     pub fn file<T: AsRef<str>>(path: T) -> Self {
         Self(Priv::File(path.as_ref().to_owned()))
     }
+
+    /// Connect a pipe to the input.  The write end of the pipe can be
+    /// taken with [StartedRun::take_stdin](crate::StartedRun::take_stdin)
+    /// once the transient service is started.
+    ///
+    /// The read end of the pipe is passed to the service manager through
+    /// D-Bus, so the D-Bus connection must support passing UNIX file
+    /// descriptors.
+    ///
+    /// This setting will be unavailable if the feature `systemd_236` is
+    /// disabled.
+    #[cfg(feature = "systemd_236")]
+    pub fn pipe() -> Self {
+        Self(Priv::Pipe)
+    }
+
+    /// Use the data as the input.  The data is sent to the service
+    /// manager along with the other settings, so this is only suitable
+    /// for small inputs.  If the size of the data exceeds the limit of
+    /// the service manager (64 MiB), [None] will be returned.
+    ///
+    /// Read `StandardInputData=` in [systemd.exec(5)](man:systemd.exec(5))
+    /// for details.
+    ///
+    /// This setting will be unavailable if the feature `systemd_236` is
+    /// disabled.
+    #[cfg(feature = "systemd_236")]
+    pub fn data<T: Into<Vec<u8>>>(data: T) -> Option<Self> {
+        // EXEC_STDIN_DATA_MAX in systemd/src/core/execute.h.
+        const MAX: usize = 64 * 1024 * 1024;
+        let data = data.into();
+        match data.len() {
+            0..=MAX => Some(Self(Priv::Data(data))),
+            _ => None,
+        }
+    }
+
+    /// Connect a file descriptor to the input, for example the read end
+    /// of a pipe created by the caller.  The file descriptor is passed
+    /// to the service manager through D-Bus and closed in this process
+    /// once the transient service is started.
+    ///
+    /// This setting will be unavailable if the feature `systemd_236` is
+    /// disabled.
+    #[cfg(feature = "systemd_236")]
+    pub fn fd(fd: OwnedFd) -> Self {
+        Self(Priv::Fd(fd))
+    }
+}
+
+// For connecting the replica of a pseudo-terminal, see RunSystem::pty.
+pub fn input_tty() -> InputSpec {
+    InputSpec(Priv::Tty)
+}
+
+pub fn output_fd(fd: OwnedFd) -> OutputSpec {
+    OutputSpec(Priv::Fd(fd))
 }
 
-pub fn marshal_input(spec: InputSpec) -> (&'static str, String) {
-    spec.0.marshal()
+// Create a pipe, returning the read end and the write end.  Both are
+// close-on-exec, as the ends for the transient service are passed
+// through D-Bus instead of being inherited.
+pub fn pipe() -> io::Result<(OwnedFd, OwnedFd)> {
+    let mut fds = [0; 2];
+    if unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_CLOEXEC) } == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(unsafe { (OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])) })
+}
+
+type Marshaled<T> = (Vec<(&'static str, Value<'static>)>, Option<T>);
+
+pub fn marshal_input(spec: Option<InputSpec>) -> io::Result<Marshaled<InputPipe>> {
+    match spec.map(|x| x.0) {
+        None => Ok((vec![], None)),
+        Some(Priv::Pipe) => {
+            let (r, w) = pipe()?;
+            let v = Value::from(Fd::from(r));
+            let w = Async::new(File::from(w))?;
+            Ok((vec![("FileDescriptor", v)], Some(InputPipe(w))))
+        }
+        Some(Priv::Fd(x)) => Ok((vec![("FileDescriptor", Value::from(Fd::from(x)))], None)),
+        Some(Priv::Data(x)) => {
+            let v = vec![("", Value::from("data")), ("Data", Value::from(x))];
+            Ok((v, None))
+        }
+        Some(x) => {
+            let (k, v) = x.marshal();
+            Ok((vec![(k, Value::from(v))], None))
+        }
+    }
 }
 
 /// The description of a output.
@@ -100,8 +204,139 @@ impl OutputSpec {
     pub fn append<T: AsRef<str>>(path: T) -> Self {
         Self(Priv::Append(path.as_ref().to_owned()))
     }
+
+    /// Connect a pipe to the output.  The read end of the pipe can be
+    /// taken with
+    /// [StartedRun::take_stdout](crate::StartedRun::take_stdout) or
+    /// [StartedRun::take_stderr](crate::StartedRun::take_stderr) once the
+    /// transient service is started.
+    ///
+    /// The write end of the pipe is passed to the service manager through
+    /// D-Bus, so the D-Bus connection must support passing UNIX file
+    /// descriptors.
+    ///
+    /// This setting will be unavailable if the feature `systemd_236` is
+    /// disabled.
+    #[cfg(feature = "systemd_236")]
+    pub fn pipe() -> Self {
+        Self(Priv::Pipe)
+    }
+
+    /// Connect a file descriptor to the output, for example the write end
+    /// of a pipe created by the caller.  The file descriptor is passed
+    /// to the service manager through D-Bus and closed in this process
+    /// once the transient service is started.
+    ///
+    /// This setting will be unavailable if the feature `systemd_236` is
+    /// disabled.
+    #[cfg(feature = "systemd_236")]
+    pub fn fd(fd: OwnedFd) -> Self {
+        Self(Priv::Fd(fd))
+    }
+}
+
+pub fn marshal_output(spec: Option<OutputSpec>) -> io::Result<Marshaled<OutputPipe>> {
+    match spec.map(|x| x.0) {
+        None => Ok((vec![], None)),
+        Some(Priv::Pipe) => {
+            let (r, w) = pipe()?;
+            let v = Value::from(Fd::from(w));
+            let r = Async::new(File::from(r))?;
+            Ok((vec![("FileDescriptor", v)], Some(OutputPipe(r))))
+        }
+        Some(Priv::Fd(x)) => Ok((vec![("FileDescriptor", Value::from(Fd::from(x)))], None)),
+        Some(x) => {
+            let (k, v) = x.marshal();
+            Ok((vec![(k, Value::from(v))], None))
+        }
+    }
+}
+
+/// The write end of a pipe connected to the input of a transient service,
+/// created with [InputSpec::pipe].
+///
+/// Drop it or close it with
+/// [AsyncWriteExt::close](futures::io::AsyncWriteExt::close) to send EOF
+/// to the transient service.
+pub struct InputPipe(Async<File>);
+
+impl AsyncWrite for InputPipe {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.0).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_close(cx)
+    }
+}
+
+/// The read end of a pipe connected to the output of a transient
+/// service, created with [OutputSpec::pipe].
+///
+/// The transient service will be blocked writing the output if the pipe
+/// is full, so keep reading it while waiting for the transient service.
+pub struct OutputPipe(Async<File>);
+
+impl AsyncRead for OutputPipe {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.0).poll_read(cx, buf)
+    }
 }
 
-pub fn marshal_output(spec: OutputSpec) -> (&'static str, String) {
-    spec.0.marshal()
+impl OutputPipe {
+    /// Read the output until EOF.  If `cap` is not [None], only the first
+    /// `cap` bytes are kept and the remaining output is discarded, so the
+    /// transient service won't be blocked.
+    pub async fn capture(mut self, cap: Option<usize>) -> io::Result<CapturedOutput> {
+        let cap = cap.unwrap_or(usize::MAX);
+        let mut data = vec![];
+        let mut truncated = false;
+        let mut buf = [0u8; 4096];
+        loop {
+            let n = self.read(&mut buf).await?;
+            if n == 0 {
+                break;
+            }
+            let keep = std::cmp::min(n, cap - data.len());
+            data.extend_from_slice(&buf[..keep]);
+            truncated |= keep < n;
+        }
+        Ok(CapturedOutput { data, truncated })
+    }
+}
+
+/// The output captured with [OutputPipe::capture].
+#[derive(Debug, Clone)]
+pub struct CapturedOutput {
+    data: Vec<u8>,
+    truncated: bool,
+}
+
+impl CapturedOutput {
+    /// Get the captured output.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Consume the [CapturedOutput], returning the captured output.
+    pub fn into_data(self) -> Vec<u8> {
+        self.data
+    }
+
+    /// Check if some output is discarded because of the cap.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
 }
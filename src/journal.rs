@@ -0,0 +1,177 @@
+use async_process::Command;
+use std::collections::HashMap;
+use std::io;
+use std::time::{Duration, SystemTime};
+use uuid::Uuid;
+
+/// The log level, i. e. the syslog priority of a log message.
+///
+/// Read `LogLevelMax=` in [systemd.exec(5)](man:systemd.exec(5)) for
+/// details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    /// System is unusable.
+    Emerg,
+    /// Action must be taken immediately.
+    Alert,
+    /// Critical conditions.
+    Crit,
+    /// Error conditions.
+    Err,
+    /// Warning conditions.
+    Warning,
+    /// Normal but significant conditions.
+    Notice,
+    /// Informational messages.
+    Info,
+    /// Debug-level messages.
+    Debug,
+}
+
+impl LogLevel {
+    /// Get the syslog priority of the log level, from 0 for
+    /// [LogLevel::Emerg] to 7 for [LogLevel::Debug].
+    pub fn priority(self) -> i32 {
+        self as i32
+    }
+
+    fn from_priority(p: &[u8]) -> Option<Self> {
+        use LogLevel::*;
+        Some(match p {
+            b"0" => Emerg,
+            b"1" => Alert,
+            b"2" => Crit,
+            b"3" => Err,
+            b"4" => Warning,
+            b"5" => Notice,
+            b"6" => Info,
+            b"7" => Debug,
+            _ => return None,
+        })
+    }
+}
+
+/// An entry read from the journal, produced by
+/// [FinishedRun::journal](crate::FinishedRun::journal).
+///
+/// Read [systemd.journal-fields(7)](man:systemd.journal-fields(7)) for
+/// the meaning of the fields.
+#[derive(Debug, Clone, Default)]
+pub struct JournalEntry {
+    fields: HashMap<String, Vec<u8>>,
+}
+
+impl JournalEntry {
+    /// Get the raw value of a field, for example `MESSAGE` or a field
+    /// added with [RunSystem::log_extra_fields](crate::RunSystem::log_extra_fields).
+    pub fn field(&self, name: &str) -> Option<&[u8]> {
+        self.fields.get(name).map(Vec::as_slice)
+    }
+
+    /// Get the message, from `MESSAGE`.  Invalid UTF-8 sequences are
+    /// replaced with `U+FFFD`.
+    pub fn message(&self) -> Option<String> {
+        self.field("MESSAGE")
+            .map(|x| String::from_utf8_lossy(x).into_owned())
+    }
+
+    /// Get the log level of the message, from `PRIORITY`.
+    pub fn level(&self) -> Option<LogLevel> {
+        self.field("PRIORITY").and_then(LogLevel::from_priority)
+    }
+
+    /// Get the time when the entry is received by the journal, from
+    /// `__REALTIME_TIMESTAMP`.
+    pub fn time(&self) -> Option<SystemTime> {
+        let us = std::str::from_utf8(self.field("__REALTIME_TIMESTAMP")?).ok()?;
+        let us = us.parse().ok()?;
+        SystemTime::UNIX_EPOCH.checked_add(Duration::from_micros(us))
+    }
+}
+
+// Parse the journal export format.  Read
+// https://systemd.io/JOURNAL_EXPORT_FORMATS/ for details.
+fn parse_export(mut s: &[u8]) -> io::Result<Vec<JournalEntry>> {
+    let bad = || io::Error::new(io::ErrorKind::InvalidData, "malformed journal export");
+    let mut entries = vec![];
+    let mut entry = JournalEntry::default();
+
+    while !s.is_empty() {
+        let eol = s.iter().position(|&c| c == b'\n').ok_or_else(bad)?;
+        let line = &s[..eol];
+        s = &s[eol + 1..];
+
+        if line.is_empty() {
+            entries.push(std::mem::take(&mut entry));
+            continue;
+        }
+
+        let (k, v) = match line.iter().position(|&c| c == b'=') {
+            Some(p) => (&line[..p], line[p + 1..].to_vec()),
+            None => {
+                // A binary field: the name, then the size as a
+                // little-endian u64, the data, and a newline.
+                let size = s.get(..8).ok_or_else(bad)?;
+                let size = u64::from_le_bytes(size.try_into().unwrap());
+                let size = usize::try_from(size).map_err(|_| bad())?;
+                let v = s.get(8..8 + size).ok_or_else(bad)?.to_vec();
+                s = s.get(8 + size + 1..).ok_or_else(bad)?;
+                (line, v)
+            }
+        };
+        let k = String::from_utf8(k.to_vec()).map_err(|_| bad())?;
+        entry.fields.insert(k, v);
+    }
+
+    if !entry.fields.is_empty() {
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+// Read journal_field_valid in systemd/src/libsystemd/sd-journal/journal-file.c.
+// The fields with a leading underscore are trusted and can't be set by
+// the client.
+pub fn is_valid_field_name(s: &str) -> bool {
+    let ok = |c: u8| c.is_ascii_uppercase() || c.is_ascii_digit() || c == b'_';
+    match s.as_bytes() {
+        [] => false,
+        [b'_' | b'0'..=b'9', ..] => false,
+        x => x.len() <= 64 && x.iter().all(|&c| ok(c)),
+    }
+}
+
+// Read the entries of a run with `invocation_id`.  `user_unit` is the
+// name of the unit if it's run by the per-user service manager.
+pub async fn read(invocation_id: Uuid, user_unit: Option<&str>) -> io::Result<Vec<JournalEntry>> {
+    let id = invocation_id.simple();
+    let matches = match user_unit {
+        None => vec![format!("_SYSTEMD_INVOCATION_ID={}", id)],
+        // The processes of a user unit are in the control group of the
+        // user@.service unit, and depending on the version of systemd the
+        // invocation ID of the user unit is logged with either field.
+        // Without --user, both the user journal and the system journal
+        // (where the entries go if the journal is not split per user)
+        // are read.
+        Some(unit) => vec![
+            format!("_SYSTEMD_USER_UNIT={}", unit),
+            format!("_SYSTEMD_INVOCATION_ID={}", id),
+            "+".to_owned(),
+            format!("_SYSTEMD_USER_UNIT={}", unit),
+            format!("USER_INVOCATION_ID={}", id),
+        ],
+    };
+    let out = Command::new("journalctl")
+        .args(["--no-pager", "-o", "export"])
+        .args(matches)
+        .output()
+        .await?;
+    if !out.status.success() {
+        let msg = String::from_utf8_lossy(&out.stderr);
+        return Err(io::Error::other(format!(
+            "journalctl failed: {}",
+            msg.trim()
+        )));
+    }
+    parse_export(&out.stdout)
+}
@@ -1,24 +1,62 @@
 #![doc = include_str!("../README.md")]
 
 use byte_unit::Byte;
+use std::collections::VecDeque;
 use std::num::NonZeroU64;
+use std::sync::Mutex;
 use std::time::Duration;
+use uuid::Uuid;
 use zbus::fdo::{PropertiesChangedStream, PropertiesProxy};
 use zbus::zvariant::{ObjectPath, Value};
 use zbus::Connection;
 
+mod capability;
+mod cgroup;
 mod cpu_sched;
+mod cpuset;
+mod env;
 mod error;
+mod event;
 mod identity;
+#[cfg(feature = "systemd_236")]
+mod interactive;
+mod io_sched;
+mod iocontrol;
 mod ioredirect;
+mod journal;
+mod manager;
+mod memory;
 mod mount;
+mod protect;
+mod pty;
+mod rlimit;
+mod sample;
 mod sd;
+mod seccomp;
+mod status;
+mod verdict;
 
+pub use capability::{Capability, SecureBit};
 pub use cpu_sched::CpuScheduling;
+pub use cpuset::{CpuSet, NumaPolicy};
 pub use error::{Error, Result};
+pub use event::{ActiveState, UnitEvent};
 pub use identity::Identity;
-pub use ioredirect::{InputSpec, OutputSpec};
+#[cfg(feature = "systemd_236")]
+pub use interactive::Interaction;
+pub use io_sched::IoScheduling;
+pub use ioredirect::{CapturedOutput, InputPipe, InputSpec, OutputPipe, OutputSpec};
+pub use journal::{JournalEntry, LogLevel};
+pub use manager::Manager;
+pub use memory::{ManagedOom, ManagedOomPreference, MemoryLimit, OomPolicy};
 pub use mount::Mount;
+pub use protect::{AccessPath, ProtectHome, ProtectSystem};
+pub use pty::Pty;
+pub use rlimit::{Rlimit, RlimitValue};
+pub use sample::ResourceSample;
+pub use seccomp::SystemCallFilter;
+pub use status::{ExitStatus, ServiceResult};
+pub use verdict::{TimeLimit, Verdict};
 
 #[allow(dead_code)]
 enum ProtectProcInternal {
@@ -61,8 +99,23 @@ impl Default for ProtectProc {
     }
 }
 
+#[allow(dead_code)]
+enum TasksMax {
+    Count(u64),
+    Percent(u8),
+}
+
 /// Information of a transient service for running on the system service
 /// manager.
+///
+/// `CPUAccounting=`, `MemoryAccounting=` and `TasksAccounting=` are
+/// always switched on for the transient service (if the feature
+/// `systemd_227` is enabled), so the resource usage can be reported by
+/// [StartedRun::sample] and [FinishedRun].  So is `IOAccounting=` if the
+/// features `unified_cgroup` and `systemd_231` are enabled.  This may
+/// enable the corresponding controllers for the parent slices as well.
+/// Some statistics in [FinishedRun] are only recorded by the samples,
+/// see [StartedRun::sample] for details.
 pub struct RunSystem {
     path: String,
     args: Vec<String>,
@@ -70,57 +123,139 @@ pub struct RunSystem {
     collect_on_fail: bool,
     identity: identity::Identity,
     runtime_max: Option<Duration>,
-    memory_max: Option<Byte>,
-    memory_swap_max: Option<Byte>,
-    allowed_cpus: Vec<usize>,
+    memory_max: Option<MemoryLimit>,
+    memory_swap_max: Option<MemoryLimit>,
+    memory_high: Option<MemoryLimit>,
+    memory_low: Option<MemoryLimit>,
+    memory_min: Option<MemoryLimit>,
+    memory_zswap_max: Option<MemoryLimit>,
+    oom: memory::Oom,
+    tasks_max: Option<TasksMax>,
+    allowed_cpus: CpuSet,
+    cpu_affinity: CpuSet,
+    allowed_memory_nodes: CpuSet,
+    numa_policy: Option<NumaPolicy>,
+    // In microseconds per second, u64::MAX for "infinity".
     cpu_quota: Option<u64>,
+    cpu_quota_period: Option<Duration>,
+    cpu_weight: Option<u64>,
+    startup_cpu_weight: Option<u64>,
+    io: iocontrol::IoControl,
     private_network: bool,
     private_ipc: bool,
     mount: Vec<(String, Mount)>,
+    protect_system: Option<ProtectSystem>,
+    protect_home: Option<ProtectHome>,
+    paths: protect::PathLists,
     mount_api_vfs: bool,
     private_devices: bool,
     no_new_privileges: bool,
-    limit_fsize: Option<Byte>,
-    limit_fsize_soft: Option<Byte>,
-    limit_stack: Option<Byte>,
-    limit_stack_soft: Option<Byte>,
-    limit_core: Option<Byte>,
-    limit_core_soft: Option<Byte>,
-    limit_nofile: Option<u64>,
-    limit_nofile_soft: Option<u64>,
-    limit_nproc: Option<u64>,
-    limit_nproc_soft: Option<u64>,
+    syscall_filter: Option<SystemCallFilter>,
+    capability_bounding_set: Option<u64>,
+    ambient_capabilities: Option<u64>,
+    secure_bits: Option<i32>,
+    rlimits: Vec<(Rlimit, RlimitValue, RlimitValue)>,
     stdin: Option<InputSpec>,
     stdout: Option<OutputSpec>,
     stderr: Option<OutputSpec>,
+    pty: bool,
     current_dir: Option<String>,
+    env: env::Environment,
+    syslog_identifier: Option<String>,
+    log_extra_fields: Vec<(String, String)>,
+    log_level_max: Option<LogLevel>,
     protect_proc: ProtectProcInternal,
     slice: Option<String>,
     private_users: bool,
     timeout_stop: Option<Duration>,
     cpu_sched: CpuScheduling,
+    io_sched: Option<IoScheduling>,
     joins_namespace_of: Vec<String>,
 }
 
 /// Information of a transient service for running on the per-user service
 /// manager.
+///
+/// The settings of the `io` controller, like [RunSystem::io_weight], are
+/// deliberately not provided, as the controller is usually not delegated
+/// to the per-user service manager and they would have no effect.
 pub struct RunUser(RunSystem);
 
 /// A transient service running.
 pub struct StartedRun<'a> {
     proxy: zbus::fdo::PropertiesProxy<'a>,
     stream: PropertiesChangedStream,
+    manager: sd::SystemdManagerProxy<'static>,
+    unit_name: String,
+    has_job: bool,
+    active_state: Option<ActiveState>,
+    pending_events: VecDeque<UnitEvent>,
+    limits: verdict::Limits,
+    on_drop: OnDrop,
+    stdin: Option<InputPipe>,
+    stdout: Option<OutputPipe>,
+    stderr: Option<OutputPipe>,
+    pty: Option<Pty>,
+    user: bool,
+    local: bool,
+    // The statistics recorded by Self::sample for FinishedRun.
+    sampled: Mutex<cgroup::Stat>,
+}
+
+enum OnDrop {
+    Nothing,
+    Stop,
+    Kill,
+}
+
+/// The processes of a transient service to be killed by
+/// [StartedRun::kill].
+pub enum KillWho {
+    /// Only the main process.
+    Main,
+    /// Only the control process, for example the process running
+    /// `ExecStopPost=`.
+    Control,
+    /// All processes in the control group of the transient service.
+    All,
+}
+
+impl KillWho {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Main => "main",
+            Self::Control => "control",
+            Self::All => "all",
+        }
+    }
 }
 
 /// A transient service finished.
 #[derive(Debug)]
 pub struct FinishedRun {
     failed: bool,
+    aborted: bool,
+    result: ServiceResult,
+    exit_status: Option<ExitStatus>,
     wall_time_usage: Duration,
+    cpu_time_usage: Option<Duration>,
+    cpu_user_time_usage: Option<Duration>,
+    cpu_system_time_usage: Option<Duration>,
+    memory_peak: Option<Byte>,
+    memory_swap_peak: Option<Byte>,
+    oom_kills: Option<u64>,
+    tasks_peak: Option<u64>,
+    io_read_bytes: Option<Byte>,
+    io_write_bytes: Option<Byte>,
+    invocation_id: Option<Uuid>,
+    user_unit: Option<String>,
+    limits: verdict::Limits,
 }
 
-// The logic is "borrowed" from systemd/src/run.c.
-fn default_unit_name(bus: &zbus::Connection) -> Result<String> {
+// The logic is "borrowed" from systemd/src/run.c.  As a connection may
+// be reused for many units, a sequence number is appended for all units
+// but the first one.
+fn default_unit_name(bus: &zbus::Connection, seq: u64) -> Result<String> {
     bus.unique_name()
         .map_or_else(
             || {
@@ -138,7 +273,10 @@ fn default_unit_name(bus: &zbus::Connection) -> Result<String> {
                 unreachable!("zbus should have rejected invalid name");
             },
         )
-        .map(|(tp, id)| format!("run-{}{}.service", tp, id))
+        .map(|(tp, id)| match seq {
+            0 => format!("run-{}{}.service", tp, id),
+            n => format!("run-{}{}-{}.service", tp, id, n),
+        })
 }
 
 fn escape_byte_for_object_path(b: u8) -> String {
@@ -238,16 +376,13 @@ impl RunUser {
     /// processes in this unit. If memory usage cannot be contained under
     /// the limit, out-of-memory killer is invoked inside the unit.
     ///
-    /// A [Byte] exceeding [u64::MAX] bytes is trimmed to [u64::MAX] bytes
-    /// silently.
-    ///
-    /// Read `MemoryMax=` in
+    /// Read [MemoryLimit] for the possible values, and `MemoryMax=` in
     /// [systemd.resource-control(5)](man:systemd.resource-control(5))
     /// for details.
     ///
     /// If the feature `systemd_231` is disabled, `MemoryLimit=` will be
     /// used instead if `MemoryMax=` for compatibility.
-    pub fn memory_max(self, d: Byte) -> Self {
+    pub fn memory_max<T: Into<MemoryLimit>>(self, d: T) -> Self {
         Self(self.0.memory_max(d))
     }
 
@@ -258,18 +393,242 @@ impl RunUser {
     /// so it's not available if the feature `unified_cgroup` is disabled.
     /// And it will be unavailable with `systemd_232` disabled.
     ///
-    /// A [Byte] exceeding [u64::MAX] bytes is trimmed to [u64::MAX] bytes
-    /// silently.
-    ///
-    /// Read `MemorySwapMax=` in
+    /// Read [MemoryLimit] for the possible values, and `MemorySwapMax=` in
     /// [systemd.resource-control(5)](man:systemd.resource-control(5))
     /// for details.
     #[cfg(feature = "unified_cgroup")]
     #[cfg(feature = "systemd_232")]
-    pub fn memory_swap_max(self, d: Byte) -> Self {
+    pub fn memory_swap_max<T: Into<MemoryLimit>>(self, d: T) -> Self {
         Self(self.0.memory_swap_max(d))
     }
 
+    /// Specify the throttling limit on memory usage of the executed
+    /// processes in this unit.  If the limit is exceeded, the processes
+    /// are throttled and put under heavy reclaim pressure, but the
+    /// out-of-memory killer is not invoked.
+    ///
+    /// This setting is supported only if the unified control group is
+    /// used, so it's not available if the feature `unified_cgroup` is
+    /// disabled.
+    ///
+    /// Read [MemoryLimit] for the possible values, and `MemoryHigh=` in
+    /// [systemd.resource-control(5)](man:systemd.resource-control(5))
+    /// for details.
+    #[cfg(feature = "unified_cgroup")]
+    pub fn memory_high<T: Into<MemoryLimit>>(self, d: T) -> Self {
+        Self(self.0.memory_high(d))
+    }
+
+    /// Specify the best-effort memory usage protection of the executed
+    /// processes in this unit.
+    ///
+    /// This setting is supported only if the unified control group is
+    /// used, so it's not available if the feature `unified_cgroup` is
+    /// disabled.
+    ///
+    /// Read [RunSystem::memory_low] for details.  Note that the
+    /// protection is also limited by the protection of the slice
+    /// containing the per-user service manager.
+    #[cfg(feature = "unified_cgroup")]
+    pub fn memory_low<T: Into<MemoryLimit>>(self, d: T) -> Self {
+        Self(self.0.memory_low(d))
+    }
+
+    /// Specify the hard memory usage protection of the executed processes
+    /// in this unit.
+    ///
+    /// This setting is supported only if the unified control group is
+    /// used, so it's not available if the feature `unified_cgroup` is
+    /// disabled.  And, if `systemd_240` is disabled, this setting will
+    /// also be unavailable.
+    ///
+    /// Read [RunSystem::memory_min] for details.  Note that the
+    /// protection is also limited by the protection of the slice
+    /// containing the per-user service manager.
+    #[cfg(feature = "unified_cgroup")]
+    #[cfg(feature = "systemd_240")]
+    pub fn memory_min<T: Into<MemoryLimit>>(self, d: T) -> Self {
+        Self(self.0.memory_min(d))
+    }
+
+    /// Specify the absolute limit on zswap usage of the executed
+    /// processes in this unit.
+    ///
+    /// This setting is supported only if the unified control group is
+    /// used, so it's not available if the feature `unified_cgroup` is
+    /// disabled.  And, if `systemd_253` is disabled, this setting will
+    /// also be unavailable.
+    ///
+    /// Read [MemoryLimit] for the possible values, and `MemoryZSwapMax=`
+    /// in [systemd.resource-control(5)](man:systemd.resource-control(5))
+    /// for details.
+    #[cfg(feature = "unified_cgroup")]
+    #[cfg(feature = "systemd_253")]
+    pub fn memory_zswap_max<T: Into<MemoryLimit>>(self, d: T) -> Self {
+        Self(self.0.memory_zswap_max(d))
+    }
+
+    /// Specify the maximum number of tasks (processes and threads) of the
+    /// transient service.  Unlike [Self::limit_nproc], this is counted
+    /// for the transient service only, instead of all the processes of
+    /// the user.
+    ///
+    /// Read `TasksMax=` in
+    /// [systemd.resource-control(5)](man:systemd.resource-control(5))
+    /// for details.
+    ///
+    /// This setting is unavailable with the feature `systemd_227`
+    /// disabled.
+    #[cfg(feature = "systemd_227")]
+    pub fn tasks_max(self, n: NonZeroU64) -> Self {
+        Self(self.0.tasks_max(n))
+    }
+
+    /// Like [Self::tasks_max], but specify the maximum number of tasks as
+    /// a percentage of the maximum number of tasks on the system.
+    ///
+    /// A value out of `[1, 100]` will be trimmed into the range silently.
+    ///
+    /// This setting is unavailable with the feature `systemd_236`
+    /// disabled.
+    #[cfg(feature = "systemd_236")]
+    pub fn tasks_max_percent(self, percent: u8) -> Self {
+        Self(self.0.tasks_max_percent(percent))
+    }
+
+    /// Set the CPU affinity of the executed processes with
+    /// `sched_setaffinity(2)`.  A [CpuSet] can be used as `cpus`.
+    ///
+    /// Read [RunSystem::cpu_affinity] for details.
+    ///
+    /// This setting is unavailable with the feature `systemd_236`
+    /// disabled.
+    #[cfg(feature = "systemd_236")]
+    pub fn cpu_affinity<'a, I: IntoIterator<Item = &'a usize>>(self, cpus: I) -> Self {
+        Self(self.0.cpu_affinity(cpus))
+    }
+
+    /// Set the NUMA memory policy of the executed processes, see
+    /// [NumaPolicy].
+    ///
+    /// This setting is unavailable with the feature `systemd_244`
+    /// disabled.
+    #[cfg(feature = "systemd_244")]
+    pub fn numa_policy(self, policy: NumaPolicy) -> Self {
+        Self(self.0.numa_policy(policy))
+    }
+
+    /// Specify IO scheduling class and priority.
+    /// See [IoScheduling] for details.
+    pub fn io_schedule(self, io_sched: IoScheduling) -> Self {
+        Self(self.0.io_schedule(io_sched))
+    }
+
+    /// Configure the action when the out-of-memory killer is invoked for
+    /// the transient service, see [OomPolicy].
+    ///
+    /// Read `OOMPolicy=` in [systemd.service(5)](man:systemd.service(5))
+    /// for details.
+    ///
+    /// This setting is unavailable with the feature `systemd_244`
+    /// disabled.
+    #[cfg(feature = "systemd_244")]
+    pub fn oom_policy(self, policy: OomPolicy) -> Self {
+        Self(self.0.oom_policy(policy))
+    }
+
+    /// Set the adjustment level for the out-of-memory killer of the kernel
+    /// for the executed processes, in `[-1000, 1000]`.
+    ///
+    /// A value out of the range will be trimmed into the range silently.
+    ///
+    /// Read `OOMScoreAdjust=` in [systemd.exec(5)](man:systemd.exec(5))
+    /// for details.
+    ///
+    /// Unlike [RunSystem::oom_score_adjust], this can't be used to
+    /// decrease the level below the level of the per-user service manager
+    /// because of insufficient privileges.
+    pub fn oom_score_adjust(self, adj: i32) -> Self {
+        Self(self.0.oom_score_adjust(adj))
+    }
+
+    /// Configure the action of `systemd-oomd` when the swap usage of the
+    /// system is too high, see [ManagedOom].
+    ///
+    /// Read `ManagedOOMSwap=` in
+    /// [systemd.resource-control(5)](man:systemd.resource-control(5))
+    /// for details.
+    ///
+    /// This setting is unavailable with the feature `systemd_247`
+    /// disabled.
+    #[cfg(feature = "systemd_247")]
+    pub fn managed_oom_swap(self, x: ManagedOom) -> Self {
+        Self(self.0.managed_oom_swap(x))
+    }
+
+    /// Configure the action of `systemd-oomd` when the memory pressure of
+    /// the transient service is too high, see [ManagedOom].
+    ///
+    /// Read `ManagedOOMMemoryPressure=` in
+    /// [systemd.resource-control(5)](man:systemd.resource-control(5))
+    /// for details.
+    ///
+    /// This setting is unavailable with the feature `systemd_247`
+    /// disabled.
+    #[cfg(feature = "systemd_247")]
+    pub fn managed_oom_memory_pressure(self, x: ManagedOom) -> Self {
+        Self(self.0.managed_oom_memory_pressure(x))
+    }
+
+    /// Set the memory pressure limit in percent for
+    /// [Self::managed_oom_memory_pressure].
+    ///
+    /// A value greater than 100 will be trimmed to 100 silently.
+    ///
+    /// Read `ManagedOOMMemoryPressureLimit=` in
+    /// [systemd.resource-control(5)](man:systemd.resource-control(5))
+    /// for details.
+    ///
+    /// This setting is unavailable with the feature `systemd_248`
+    /// disabled.
+    #[cfg(feature = "systemd_248")]
+    pub fn managed_oom_memory_pressure_limit(self, percent: u8) -> Self {
+        Self(self.0.managed_oom_memory_pressure_limit(percent))
+    }
+
+    /// Restrict the system calls of the executed processes with seccomp.
+    /// See [SystemCallFilter] for details.
+    ///
+    /// `NoNewPrivileges=` is implied by the per-user service manager for
+    /// it.
+    ///
+    /// This setting is unavailable with the feature `systemd_236`
+    /// disabled.
+    #[cfg(feature = "systemd_236")]
+    pub fn system_call_filter(self, filter: SystemCallFilter) -> Self {
+        Self(self.0.system_call_filter(filter))
+    }
+
+    /// Set soft and hard limits of a resource.  The unit of the values
+    /// depends on the resource, read [Rlimit] for details.  Setting the
+    /// same resource again overrides the previous setting.
+    ///
+    /// If `soft` is greater than `hard`, it will be trimmed to `hard`
+    /// silently.
+    ///
+    /// Read `LimitCPU=` etc. in [systemd.exec(5)](man:systemd.exec(5)) and
+    /// [prlimit(2)](man:prlimit(2)) for details.
+    ///
+    /// Unlike [RunSystem::limit], this can't be used to increase the hard
+    /// limit because of insufficient privileges.
+    pub fn limit<S, H>(self, resource: Rlimit, soft: S, hard: H) -> Self
+    where
+        S: Into<RlimitValue>,
+        H: Into<RlimitValue>,
+    {
+        Self(self.0.limit(resource, soft, hard))
+    }
+
     /// Set soft and hard limits of the maximum size in bytes of files that
     /// the process may create.
     ///
@@ -404,6 +763,22 @@ impl RunUser {
         Self(self.0.stderr(spec))
     }
 
+    /// Connect the input and outputs of the executed processes to a newly
+    /// allocated pseudo-terminal, like `systemd-run --pty`.  The
+    /// controller side can be taken with [StartedRun::take_pty] once the
+    /// transient service is started.
+    ///
+    /// This overrides [Self::stdin], [Self::stdout], and [Self::stderr].
+    /// Read `StandardInput=` and `TTYPath=` in
+    /// [systemd.exec(5)](man:systemd.exec(5)) for details.
+    ///
+    /// This setting is unavailable with the feature `systemd_236`
+    /// disabled.
+    #[cfg(feature = "systemd_236")]
+    pub fn pty(self) -> Self {
+        Self(self.0.pty())
+    }
+
     /// Sets the working directory for executed processes.
     ///
     /// Read `WorkingDirectory=` in
@@ -416,6 +791,117 @@ impl RunUser {
         Self(self.0.current_dir(path))
     }
 
+    /// Set an environment variable for executed processes.  If the
+    /// variable is set multiple times, the last value is used.
+    ///
+    /// The name of the variable must not be empty and must not contain
+    /// `=` or NUL, and the value must not contain NUL.  Or
+    /// [Error::InvalidEnvironment] will be returned when starting the
+    /// transient service.
+    ///
+    /// Read `Environment=` in [systemd.exec(5)](man:systemd.exec(5)) for
+    /// details.
+    pub fn env<K: AsRef<str>, V: AsRef<str>>(self, key: K, value: V) -> Self {
+        Self(self.0.env(key, value))
+    }
+
+    /// Set multiple environment variables for executed processes, like
+    /// calling [Self::env] for each of them.
+    pub fn envs<I, K, V>(self, vars: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        Self(self.0.envs(vars))
+    }
+
+    /// Remove an environment variable for executed processes, even if it
+    /// is set by the service manager or an environment file.
+    ///
+    /// Read `UnsetEnvironment=` in [systemd.exec(5)](man:systemd.exec(5))
+    /// for details.
+    ///
+    /// This setting is unavailable with the feature `systemd_236`
+    /// disabled.
+    #[cfg(feature = "systemd_236")]
+    pub fn env_remove<K: AsRef<str>>(self, key: K) -> Self {
+        Self(self.0.env_remove(key))
+    }
+
+    /// Read environment variables for executed processes from a file.
+    /// The file must exist when the transient service is started.
+    ///
+    /// Read `EnvironmentFile=` in [systemd.exec(5)](man:systemd.exec(5))
+    /// for details.
+    ///
+    /// This setting is unavailable with the feature `systemd_236`
+    /// disabled.
+    #[cfg(feature = "systemd_236")]
+    pub fn env_file<P: AsRef<str>>(self, path: P) -> Self {
+        Self(self.0.env_file(path))
+    }
+
+    /// Pass environment variables from the per-user service manager to
+    /// executed processes.  The variables not set for the service manager
+    /// are ignored.
+    ///
+    /// Read `PassEnvironment=` in [systemd.exec(5)](man:systemd.exec(5))
+    /// for details.
+    ///
+    /// This setting is unavailable with the feature `systemd_229`
+    /// disabled.
+    #[cfg(feature = "systemd_229")]
+    pub fn pass_environment<I: IntoIterator<Item = T>, T: AsRef<str>>(self, names: I) -> Self {
+        Self(self.0.pass_environment(names))
+    }
+
+    /// Sets the process name ("syslog tag") to prefix log lines sent to
+    /// the logging system or the kernel log buffer with.  The default is
+    /// the process name of the executed process.
+    ///
+    /// Read `SyslogIdentifier=` in [systemd.exec(5)](man:systemd.exec(5))
+    /// for details.
+    ///
+    /// This setting is unavailable with the feature `systemd_231`
+    /// disabled.
+    #[cfg(feature = "systemd_231")]
+    pub fn syslog_identifier<S: AsRef<str>>(self, id: S) -> Self {
+        Self(self.0.syslog_identifier(id))
+    }
+
+    /// Add extra fields to all log records written by the executed
+    /// processes.  The field names must consist of uppercase letters,
+    /// digits, and underscores, and must not begin with an underscore or
+    /// a digit.
+    ///
+    /// Read `LogExtraFields=` in [systemd.exec(5)](man:systemd.exec(5))
+    /// for details.
+    ///
+    /// This setting is unavailable with the feature `systemd_236`
+    /// disabled.
+    #[cfg(feature = "systemd_236")]
+    pub fn log_extra_fields<I, K, V>(self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        Self(self.0.log_extra_fields(fields))
+    }
+
+    /// Drop the log messages with a lower level than the [LogLevel].
+    ///
+    /// Read `LogLevelMax=` in [systemd.exec(5)](man:systemd.exec(5)) for
+    /// details.
+    ///
+    /// This setting is unavailable with the feature `systemd_236`
+    /// disabled.
+    #[cfg(feature = "systemd_236")]
+    pub fn log_level_max(self, level: LogLevel) -> Self {
+        Self(self.0.log_level_max(level))
+    }
+
     /// Put the transient service into a slice.
     ///
     /// Read `Slice=` in
@@ -456,6 +942,10 @@ impl RunUser {
     }
 
     /// Start the transient service.
+    ///
+    /// A new D-Bus connection is opened to the session bus for the
+    /// transient service.  Use [Manager::start_user] to reuse a
+    /// connection for many transient services.
     pub async fn start<'a>(self) -> Result<StartedRun<'a>> {
         self.0.start().await
     }
@@ -473,33 +963,50 @@ impl RunSystem {
             runtime_max: None,
             memory_max: None,
             memory_swap_max: None,
-            allowed_cpus: vec![],
+            memory_high: None,
+            memory_low: None,
+            memory_min: None,
+            memory_zswap_max: None,
+            oom: memory::Oom::default(),
+            tasks_max: None,
+            allowed_cpus: CpuSet::new(),
+            cpu_affinity: CpuSet::new(),
+            allowed_memory_nodes: CpuSet::new(),
+            numa_policy: None,
             cpu_quota: None,
+            cpu_quota_period: None,
+            cpu_weight: None,
+            startup_cpu_weight: None,
+            io: iocontrol::IoControl::default(),
             private_network: false,
             private_ipc: false,
             mount: vec![],
+            protect_system: None,
+            protect_home: None,
+            paths: protect::PathLists::default(),
             mount_api_vfs: false,
             private_devices: false,
             no_new_privileges: false,
-            limit_fsize: None,
-            limit_fsize_soft: None,
-            limit_stack: None,
-            limit_stack_soft: None,
-            limit_nofile: None,
-            limit_nofile_soft: None,
-            limit_nproc: None,
-            limit_nproc_soft: None,
-            limit_core: None,
-            limit_core_soft: None,
+            syscall_filter: None,
+            capability_bounding_set: None,
+            ambient_capabilities: None,
+            secure_bits: None,
+            rlimits: vec![],
             stdin: None,
             stdout: None,
             stderr: None,
+            pty: false,
             current_dir: None,
+            env: env::Environment::default(),
+            syslog_identifier: None,
+            log_extra_fields: vec![],
+            log_level_max: None,
             protect_proc: ProtectProcInternal::Default,
             slice: None,
             private_users: false,
             timeout_stop: None,
             cpu_sched: CpuScheduling::default(),
+            io_sched: None,
             joins_namespace_of: vec![],
         }
     }
@@ -571,17 +1078,14 @@ impl RunSystem {
     /// processes in this unit. If memory usage cannot be contained under
     /// the limit, out-of-memory killer is invoked inside the unit.
     ///
-    /// A [Byte] exceeding [u64::MAX] bytes is trimmed to [u64::MAX] bytes
-    /// silently.
-    ///
-    /// Read `MemoryMax=` in
+    /// Read [MemoryLimit] for the possible values, and `MemoryMax=` in
     /// [systemd.resource-control(5)](man:systemd.resource-control(5))
     /// for details.
     ///
     /// If the feature `systemd_231` is disabled, `MemoryLimit=` will be
     /// used instead if `MemoryMax=` for compatibility.
-    pub fn memory_max(mut self, d: Byte) -> Self {
-        self.memory_max = Some(d);
+    pub fn memory_max<T: Into<MemoryLimit>>(mut self, d: T) -> Self {
+        self.memory_max = Some(d.into());
         self
     }
 
@@ -593,111 +1097,668 @@ impl RunSystem {
     /// And, if `systemd_232` is disabled, this setting will also be
     /// unavailable.
     ///
-    /// A [Byte] exceeding [u64::MAX] bytes is trimmed to [u64::MAX] bytes
-    /// silently.
-    ///
-    /// Read `MemorySwapMax=` in
+    /// Read [MemoryLimit] for the possible values, and `MemorySwapMax=` in
     /// [systemd.resource-control(5)](man:systemd.resource-control(5))
     /// for details.
     #[cfg(feature = "unified_cgroup")]
     #[cfg(feature = "systemd_232")]
-    pub fn memory_swap_max(mut self, d: Byte) -> Self {
-        self.memory_swap_max = Some(d);
+    pub fn memory_swap_max<T: Into<MemoryLimit>>(mut self, d: T) -> Self {
+        self.memory_swap_max = Some(d.into());
         self
     }
 
-    /// Assign the specified CPU time quota to the processes executed.
-    /// Takes a percentage value.  The percentage specifies how much CPU
-    /// time the unit shall get at maximum, relativeto the total CPU time
-    /// available on one CPU. Use values > 100 for allotting CPU time on
-    /// more than one CPU.
+    /// Specify the throttling limit on memory usage of the executed
+    /// processes in this unit.  If the limit is exceeded, the processes
+    /// are throttled and put under heavy reclaim pressure, but the
+    /// out-of-memory killer is not invoked.
     ///
-    /// The value will be trimmed to [u64::MAX] / 10000 silently if it
-    /// exceeds this upper limit.
+    /// This setting is supported only if the unified control group is
+    /// used, so it's not available if the feature `unified_cgroup` is
+    /// disabled.
     ///
-    /// Read `CPUQuota=` in
-    /// [systemd.resource-control(5)](man:systemd.resource-control(5)) for
-    /// details.
-    #[cfg(feature = "systemd_213")]
-    pub fn cpu_quota(mut self, percent: NonZeroU64) -> Self {
-        self.cpu_quota = Some(percent.into());
+    /// Read [MemoryLimit] for the possible values, and `MemoryHigh=` in
+    /// [systemd.resource-control(5)](man:systemd.resource-control(5))
+    /// for details.
+    #[cfg(feature = "unified_cgroup")]
+    pub fn memory_high<T: Into<MemoryLimit>>(mut self, d: T) -> Self {
+        self.memory_high = Some(d.into());
         self
     }
 
-    /// Restrict processes to be executed on specific CPUs.
-    ///
-    /// This setting doesn't guarantee that
-    /// all of the CPUs will be used by the processes as it may be limited
-    /// by parent units.
-    ///
-    /// Setting an empty list of CPUs will allow the processes of the unit
-    /// to run on **all** CPUs.  This is also the default behavior if this
-    /// is not used.
+    /// Specify the best-effort memory usage protection of the executed
+    /// processes in this unit.  The memory below the limit is not
+    /// reclaimed unless there is no unprotected memory to reclaim.
     ///
-    /// Referring to an offline or non-existing CPU in this setting causes
-    /// Systemd to **ignore this setting silently**.
+    /// This setting is supported only if the unified control group is
+    /// used, so it's not available if the feature `unified_cgroup` is
+    /// disabled.
     ///
-    /// Read `AllowedCPUs=` in
+    /// Read [MemoryLimit] for the possible values, and `MemoryLow=` in
     /// [systemd.resource-control(5)](man:systemd.resource-control(5))
     /// for details.
-    ///
-    /// This setting is supported only if the unified control group is used,
-    /// so it's not available if the feature `unified_cgroup` is disabled.
-    /// And, this setting is not available if the feature `systemd_244` is
-    /// disabled.
-    #[cfg(feature = "systemd_244")]
     #[cfg(feature = "unified_cgroup")]
-    pub fn allowed_cpus<'a, I: IntoIterator<Item = &'a usize>>(mut self, cpus: I) -> Self {
-        self.allowed_cpus = cpus.into_iter().copied().collect();
+    pub fn memory_low<T: Into<MemoryLimit>>(mut self, d: T) -> Self {
+        self.memory_low = Some(d.into());
         self
     }
 
-    /// If this setting is used, sets up a new network namespace
-    /// for the executed processes and configures only the loopback network
-    /// device "lo" inside it. No other network devices will be available
-    /// to the executed process. This is useful to turn off network access
-    /// by the executed process.
+    /// Specify the hard memory usage protection of the executed processes
+    /// in this unit.  The memory below the limit is never reclaimed.
     ///
-    /// Read `PrivateNetwork=` in [systemd.exec(5)](man:systemd.exec(5)) for
-    /// details.
+    /// This setting is supported only if the unified control group is
+    /// used, so it's not available if the feature `unified_cgroup` is
+    /// disabled.  And, if `systemd_240` is disabled, this setting will
+    /// also be unavailable.
     ///
-    /// This setting is not available if the feature `systemd_227` is
-    /// disabled.  And, it will be ignored silently if `CONFIG_NET_NS` is
-    /// not enabled in the configuration of the running kernel.
-    #[cfg(feature = "systemd_227")]
-    pub fn private_network(mut self) -> Self {
-        self.private_network = true;
+    /// Read [MemoryLimit] for the possible values, and `MemoryMin=` in
+    /// [systemd.resource-control(5)](man:systemd.resource-control(5))
+    /// for details.
+    #[cfg(feature = "unified_cgroup")]
+    #[cfg(feature = "systemd_240")]
+    pub fn memory_min<T: Into<MemoryLimit>>(mut self, d: T) -> Self {
+        self.memory_min = Some(d.into());
         self
     }
 
-    /// If this setting is used, sets up a new IPC namespace
-    /// for the executed processes. Each IPC namespace has its own set of
-    /// System V IPC identifiers and its own POSIX message queue file
-    /// system. This is useful to avoid name clash of IPC identifiers.
+    /// Specify the absolute limit on zswap usage of the executed
+    /// processes in this unit.
     ///
-    /// Read `PrivateIPC=` in [systemd.exec(5)](man:systemd.exec(5)) for
-    /// details.
+    /// This setting is supported only if the unified control group is
+    /// used, so it's not available if the feature `unified_cgroup` is
+    /// disabled.  And, if `systemd_253` is disabled, this setting will
+    /// also be unavailable.
     ///
-    /// This setting is not available if the feature `systemd_249` is
-    /// disabled.  And, it will be ignored silently if `CONFIG_IPC_NS` is
-    /// not enabled in the configuration of the running kernel.
-    #[cfg(feature = "systemd_248")]
-    pub fn private_ipc(mut self) -> Self {
-        self.private_ipc = true;
+    /// Read [MemoryLimit] for the possible values, and `MemoryZSwapMax=`
+    /// in [systemd.resource-control(5)](man:systemd.resource-control(5))
+    /// for details.
+    #[cfg(feature = "unified_cgroup")]
+    #[cfg(feature = "systemd_253")]
+    pub fn memory_zswap_max<T: Into<MemoryLimit>>(mut self, d: T) -> Self {
+        self.memory_zswap_max = Some(d.into());
         self
     }
 
-    /// Set up a mount point for the transient service.  See [Mount] for
-    /// details.
+    /// Specify the maximum number of tasks (processes and threads) of the
+    /// transient service.  Unlike [Self::limit_nproc], this is counted
+    /// for the transient service only, instead of all the processes of
+    /// the user, so it works with an [Identity] shared by other
+    /// processes.
     ///
-    /// This setting is not available if the feature `systemd_233` is
-    /// disabled.
-    #[cfg(feature = "systemd_233")]
+    /// Read `TasksMax=` in
+    /// [systemd.resource-control(5)](man:systemd.resource-control(5))
+    /// for details.
+    ///
+    /// This setting is unavailable with the feature `systemd_227`
+    /// disabled.
+    #[cfg(feature = "systemd_227")]
+    pub fn tasks_max(mut self, n: NonZeroU64) -> Self {
+        self.tasks_max = Some(TasksMax::Count(n.get()));
+        self
+    }
+
+    /// Like [Self::tasks_max], but specify the maximum number of tasks as
+    /// a percentage of the maximum number of tasks on the system.
+    ///
+    /// A value out of `[1, 100]` will be trimmed into the range silently.
+    ///
+    /// This setting is unavailable with the feature `systemd_236`
+    /// disabled.
+    #[cfg(feature = "systemd_236")]
+    pub fn tasks_max_percent(mut self, percent: u8) -> Self {
+        self.tasks_max = Some(TasksMax::Percent(percent.clamp(1, 100)));
+        self
+    }
+
+    /// Configure the action when the out-of-memory killer is invoked for
+    /// the transient service, see [OomPolicy].
+    ///
+    /// Read `OOMPolicy=` in [systemd.service(5)](man:systemd.service(5))
+    /// for details.
+    ///
+    /// This setting is unavailable with the feature `systemd_244`
+    /// disabled.
+    #[cfg(feature = "systemd_244")]
+    pub fn oom_policy(mut self, policy: OomPolicy) -> Self {
+        self.oom.policy = Some(policy);
+        self
+    }
+
+    /// Set the adjustment level for the out-of-memory killer of the kernel
+    /// for the executed processes, in `[-1000, 1000]`.  A greater value
+    /// makes the processes more likely to be killed.
+    ///
+    /// A value out of the range will be trimmed into the range silently.
+    ///
+    /// Read `OOMScoreAdjust=` in [systemd.exec(5)](man:systemd.exec(5))
+    /// for details.
+    pub fn oom_score_adjust(mut self, adj: i32) -> Self {
+        self.oom.score_adjust = Some(adj.clamp(-1000, 1000));
+        self
+    }
+
+    /// Configure the action of `systemd-oomd` when the swap usage of the
+    /// system is too high, see [ManagedOom].
+    ///
+    /// Read `ManagedOOMSwap=` in
+    /// [systemd.resource-control(5)](man:systemd.resource-control(5))
+    /// for details.
+    ///
+    /// This setting is unavailable with the feature `systemd_247`
+    /// disabled.
+    #[cfg(feature = "systemd_247")]
+    pub fn managed_oom_swap(mut self, x: ManagedOom) -> Self {
+        self.oom.managed_swap = Some(x);
+        self
+    }
+
+    /// Configure the action of `systemd-oomd` when the memory pressure of
+    /// the transient service is too high, see [ManagedOom].
+    ///
+    /// Read `ManagedOOMMemoryPressure=` in
+    /// [systemd.resource-control(5)](man:systemd.resource-control(5))
+    /// for details.
+    ///
+    /// This setting is unavailable with the feature `systemd_247`
+    /// disabled.
+    #[cfg(feature = "systemd_247")]
+    pub fn managed_oom_memory_pressure(mut self, x: ManagedOom) -> Self {
+        self.oom.managed_memory_pressure = Some(x);
+        self
+    }
+
+    /// Set the memory pressure limit in percent for
+    /// [Self::managed_oom_memory_pressure].
+    ///
+    /// A value greater than 100 will be trimmed to 100 silently.
+    ///
+    /// Read `ManagedOOMMemoryPressureLimit=` in
+    /// [systemd.resource-control(5)](man:systemd.resource-control(5))
+    /// for details.
+    ///
+    /// This setting is unavailable with the feature `systemd_248`
+    /// disabled.
+    #[cfg(feature = "systemd_248")]
+    pub fn managed_oom_memory_pressure_limit(mut self, percent: u8) -> Self {
+        let percent = std::cmp::min(percent, 100);
+        self.oom.managed_memory_pressure_limit = Some(percent);
+        self
+    }
+
+    /// Configure the preference of `systemd-oomd` to kill the transient
+    /// service, see [ManagedOomPreference].
+    ///
+    /// Read `ManagedOOMPreference=` in
+    /// [systemd.resource-control(5)](man:systemd.resource-control(5))
+    /// for details.
+    ///
+    /// This setting is unavailable with the feature `systemd_248`
+    /// disabled.
+    #[cfg(feature = "systemd_248")]
+    pub fn managed_oom_preference(mut self, x: ManagedOomPreference) -> Self {
+        self.oom.managed_preference = Some(x);
+        self
+    }
+
+    /// Assign the specified CPU time quota to the processes executed.
+    /// Takes a percentage value.  The percentage specifies how much CPU
+    /// time the unit shall get at maximum, relativeto the total CPU time
+    /// available on one CPU. Use values > 100 for allotting CPU time on
+    /// more than one CPU.
+    ///
+    /// If the quota is too large to be represented in microseconds per
+    /// second, it's treated as "infinity", i. e. no quota.
+    ///
+    /// Read `CPUQuota=` in
+    /// [systemd.resource-control(5)](man:systemd.resource-control(5)) for
+    /// details.
+    #[cfg(feature = "systemd_213")]
+    pub fn cpu_quota(mut self, percent: NonZeroU64) -> Self {
+        self.cpu_quota = Some(percent.get().saturating_mul(10_000));
+        self
+    }
+
+    /// Like [Self::cpu_quota], but takes a per-mille value, for a finer
+    /// granularity.  For example, 1500 allots one and a half CPUs.
+    ///
+    /// This setting is unavailable with the feature `systemd_213`
+    /// disabled.
+    #[cfg(feature = "systemd_213")]
+    pub fn cpu_quota_permille(mut self, permille: NonZeroU64) -> Self {
+        self.cpu_quota = Some(permille.get().saturating_mul(1000));
+        self
+    }
+
+    /// Like [Self::cpu_quota], but takes a fraction `num / den` of one
+    /// CPU.  For example, `(2, 3)` allots two thirds of a CPU.  The quota
+    /// is rounded up to a whole microsecond per second.
+    ///
+    /// This setting is unavailable with the feature `systemd_213`
+    /// disabled.
+    #[cfg(feature = "systemd_213")]
+    pub fn cpu_quota_fraction(mut self, num: NonZeroU64, den: NonZeroU64) -> Self {
+        let usec = (u128::from(num.get()) * 1_000_000).div_ceil(u128::from(den.get()));
+        self.cpu_quota = Some(u64::try_from(usec).unwrap_or(u64::MAX));
+        self
+    }
+
+    /// Set the period for the CPU quota set with [Self::cpu_quota] etc.
+    /// The quota is enforced in each period, so a shorter period gives a
+    /// smoother throttling at the cost of more overhead.  The default is
+    /// 100 milliseconds.  The kernel only accepts a period in
+    /// `[1ms, 1s]`, and systemd trims the period into this range.
+    ///
+    /// Read `CPUQuotaPeriodSec=` in
+    /// [systemd.resource-control(5)](man:systemd.resource-control(5))
+    /// for details.
+    ///
+    /// This setting is unavailable with the feature `systemd_244`
+    /// disabled.
+    #[cfg(feature = "systemd_244")]
+    pub fn cpu_quota_period(mut self, d: Duration) -> Self {
+        self.cpu_quota_period = Some(d);
+        self
+    }
+
+    /// Set the CPU weight of the executed processes, in `[1, 10000]`.  The
+    /// default is 100.  The CPU time is distributed among the units in
+    /// proportion to their weights if the CPUs are busy.
+    ///
+    /// A value out of the range will be trimmed into the range silently.
+    ///
+    /// Read `CPUWeight=` in
+    /// [systemd.resource-control(5)](man:systemd.resource-control(5))
+    /// for details.
+    ///
+    /// This setting is supported only if the unified control group is used,
+    /// so it's not available if the feature `unified_cgroup` is disabled.
+    #[cfg(feature = "unified_cgroup")]
+    pub fn cpu_weight(mut self, weight: u64) -> Self {
+        self.cpu_weight = Some(weight.clamp(1, 10000));
+        self
+    }
+
+    /// Like [Self::cpu_weight], but only applies during the startup or
+    /// shutdown of the system.
+    ///
+    /// Read `StartupCPUWeight=` in
+    /// [systemd.resource-control(5)](man:systemd.resource-control(5))
+    /// for details.
+    ///
+    /// This setting is supported only if the unified control group is used,
+    /// so it's not available if the feature `unified_cgroup` is disabled.
+    #[cfg(feature = "unified_cgroup")]
+    pub fn startup_cpu_weight(mut self, weight: u64) -> Self {
+        self.startup_cpu_weight = Some(weight.clamp(1, 10000));
+        self
+    }
+
+    /// Restrict processes to be executed on specific CPUs.  A [CpuSet]
+    /// can be used as `cpus`.
+    ///
+    /// This setting doesn't guarantee that
+    /// all of the CPUs will be used by the processes as it may be limited
+    /// by parent units.
+    ///
+    /// Setting an empty list of CPUs will allow the processes of the unit
+    /// to run on **all** CPUs.  This is also the default behavior if this
+    /// is not used.
+    ///
+    /// Referring to an offline or non-existing CPU in this setting would
+    /// cause Systemd to **ignore this setting silently**, so the CPUs are
+    /// checked against the online CPUs of the host before starting the
+    /// transient service, and [Error::CpuOffline] is returned on failure.
+    /// A CPU not less than [CpuSet::MAX] is rejected with
+    /// [Error::InvalidCpuSet] even if the online CPUs can't be read.
+    ///
+    /// The online CPUs are read from `/sys` of the local host, so they
+    /// are not checked if the transient service is started with a
+    /// [Manager] connected by [Manager::address], [Manager::private] or
+    /// [Manager::from_connection], as the service manager may be on
+    /// another host or in a container.
+    ///
+    /// Read `AllowedCPUs=` in
+    /// [systemd.resource-control(5)](man:systemd.resource-control(5))
+    /// for details.
+    ///
+    /// This setting is supported only if the unified control group is used,
+    /// so it's not available if the feature `unified_cgroup` is disabled.
+    /// And, this setting is not available if the feature `systemd_244` is
+    /// disabled.
+    #[cfg(feature = "systemd_244")]
+    #[cfg(feature = "unified_cgroup")]
+    pub fn allowed_cpus<'a, I: IntoIterator<Item = &'a usize>>(mut self, cpus: I) -> Self {
+        self.allowed_cpus = cpus.into_iter().copied().collect();
+        self
+    }
+
+    /// Set the CPU affinity of the executed processes with
+    /// `sched_setaffinity(2)`.  Unlike [Self::allowed_cpus], this does
+    /// not need the `cpuset` controller, but the processes can change
+    /// the affinity themselves.  A [CpuSet] can be used as `cpus`.
+    ///
+    /// The CPUs are checked like [Self::allowed_cpus] before starting the
+    /// transient service.
+    ///
+    /// Read `CPUAffinity=` in [systemd.exec(5)](man:systemd.exec(5)) for
+    /// details.
+    ///
+    /// This setting is unavailable with the feature `systemd_236`
+    /// disabled.
+    #[cfg(feature = "systemd_236")]
+    pub fn cpu_affinity<'a, I: IntoIterator<Item = &'a usize>>(mut self, cpus: I) -> Self {
+        self.cpu_affinity = cpus.into_iter().copied().collect();
+        self
+    }
+
+    /// Restrict processes to allocate memory from specific NUMA nodes.
+    /// A [CpuSet] can be used as `nodes`.
+    ///
+    /// The nodes are checked against the online NUMA nodes of the host
+    /// before starting the transient service like [Self::allowed_cpus],
+    /// and [Error::NumaNodeOffline] is returned on failure.  A node not
+    /// less than [CpuSet::MAX] is rejected with [Error::InvalidCpuSet].
+    ///
+    /// Read `AllowedMemoryNodes=` in
+    /// [systemd.resource-control(5)](man:systemd.resource-control(5))
+    /// for details.
+    ///
+    /// This setting is supported only if the unified control group is used,
+    /// so it's not available if the feature `unified_cgroup` is disabled.
+    /// And, this setting is not available if the feature `systemd_244` is
+    /// disabled.
+    #[cfg(feature = "systemd_244")]
+    #[cfg(feature = "unified_cgroup")]
+    pub fn allowed_memory_nodes<'a, I: IntoIterator<Item = &'a usize>>(mut self, nodes: I) -> Self {
+        self.allowed_memory_nodes = nodes.into_iter().copied().collect();
+        self
+    }
+
+    /// Set the NUMA memory policy of the executed processes, see
+    /// [NumaPolicy].  The nodes are checked like
+    /// [Self::allowed_memory_nodes] before starting the transient service,
+    /// and an empty set for [NumaPolicy::Bind] or [NumaPolicy::Interleave]
+    /// is rejected with [Error::InvalidCpuSet].
+    ///
+    /// This setting is unavailable with the feature `systemd_244`
+    /// disabled.
+    #[cfg(feature = "systemd_244")]
+    pub fn numa_policy(mut self, policy: NumaPolicy) -> Self {
+        self.numa_policy = Some(policy);
+        self
+    }
+
+    /// Set the IO weight of the executed processes, in `[1, 10000]`.  The
+    /// default is 100.  The IO time is distributed among the units in
+    /// proportion to their weights.
+    ///
+    /// A value out of the range will be trimmed into the range silently.
+    ///
+    /// Read `IOWeight=` in
+    /// [systemd.resource-control(5)](man:systemd.resource-control(5))
+    /// for details.
+    ///
+    /// This setting is supported only if the unified control group is used,
+    /// so it's not available if the feature `unified_cgroup` is disabled.
+    #[cfg(feature = "unified_cgroup")]
+    pub fn io_weight(mut self, weight: u64) -> Self {
+        self.io.weight(weight);
+        self
+    }
+
+    /// Set the maximum read bandwidth in bytes per second of the
+    /// executed processes on a block device.  The device can be specified
+    /// with a path to the block device node, or a path to a file on the
+    /// file system backed by the block device.  Setting the same device
+    /// again overrides the previous setting.
+    ///
+    /// A [Byte] exceeding [u64::MAX] bytes is trimmed to [u64::MAX] bytes
+    /// silently.
+    ///
+    /// Read `IOReadBandwidthMax=` in
+    /// [systemd.resource-control(5)](man:systemd.resource-control(5))
+    /// for details.
+    ///
+    /// This setting is supported only if the unified control group is used,
+    /// so it's not available if the feature `unified_cgroup` is disabled.
+    #[cfg(feature = "unified_cgroup")]
+    pub fn io_read_bandwidth_max<P: AsRef<str>>(mut self, device: P, bytes: Byte) -> Self {
+        self.io.read_bandwidth_max(device.as_ref(), bytes.as_u64());
+        self
+    }
+
+    /// Like [Self::io_read_bandwidth_max], but for the write bandwidth.
+    ///
+    /// Read `IOWriteBandwidthMax=` in
+    /// [systemd.resource-control(5)](man:systemd.resource-control(5))
+    /// for details.
+    ///
+    /// This setting is supported only if the unified control group is used,
+    /// so it's not available if the feature `unified_cgroup` is disabled.
+    #[cfg(feature = "unified_cgroup")]
+    pub fn io_write_bandwidth_max<P: AsRef<str>>(mut self, device: P, bytes: Byte) -> Self {
+        self.io.write_bandwidth_max(device.as_ref(), bytes.as_u64());
+        self
+    }
+
+    /// Set the maximum read IO operations per second of the executed
+    /// processes on a block device, specified like
+    /// [Self::io_read_bandwidth_max].
+    ///
+    /// Read `IOReadIOPSMax=` in
+    /// [systemd.resource-control(5)](man:systemd.resource-control(5))
+    /// for details.
+    ///
+    /// This setting is supported only if the unified control group is used,
+    /// so it's not available if the feature `unified_cgroup` is disabled.
+    #[cfg(feature = "unified_cgroup")]
+    pub fn io_read_iops_max<P: AsRef<str>>(mut self, device: P, iops: u64) -> Self {
+        self.io.read_iops_max(device.as_ref(), iops);
+        self
+    }
+
+    /// Like [Self::io_read_iops_max], but for the write IO operations.
+    ///
+    /// Read `IOWriteIOPSMax=` in
+    /// [systemd.resource-control(5)](man:systemd.resource-control(5))
+    /// for details.
+    ///
+    /// This setting is supported only if the unified control group is used,
+    /// so it's not available if the feature `unified_cgroup` is disabled.
+    #[cfg(feature = "unified_cgroup")]
+    pub fn io_write_iops_max<P: AsRef<str>>(mut self, device: P, iops: u64) -> Self {
+        self.io.write_iops_max(device.as_ref(), iops);
+        self
+    }
+
+    /// Set the average IO latency target of the executed processes on a
+    /// block device, specified like [Self::io_read_bandwidth_max].  If
+    /// the latency is exceeded, the IO of other units with a looser
+    /// target on the device is throttled.
+    ///
+    /// Read `IODeviceLatencyTargetSec=` in
+    /// [systemd.resource-control(5)](man:systemd.resource-control(5))
+    /// for details.
+    ///
+    /// This setting is supported only if the unified control group is used,
+    /// so it's not available if the feature `unified_cgroup` is disabled.
+    /// And, this setting is not available if the feature `systemd_240` is
+    /// disabled.
+    #[cfg(feature = "systemd_240")]
+    #[cfg(feature = "unified_cgroup")]
+    pub fn io_device_latency_target<P: AsRef<str>>(mut self, device: P, d: Duration) -> Self {
+        self.io.device_latency_target(device.as_ref(), d);
+        self
+    }
+
+    /// If this setting is used, sets up a new network namespace
+    /// for the executed processes and configures only the loopback network
+    /// device "lo" inside it. No other network devices will be available
+    /// to the executed process. This is useful to turn off network access
+    /// by the executed process.
+    ///
+    /// Read `PrivateNetwork=` in [systemd.exec(5)](man:systemd.exec(5)) for
+    /// details.
+    ///
+    /// This setting is not available if the feature `systemd_227` is
+    /// disabled.  And, it will be ignored silently if `CONFIG_NET_NS` is
+    /// not enabled in the configuration of the running kernel.
+    #[cfg(feature = "systemd_227")]
+    pub fn private_network(mut self) -> Self {
+        self.private_network = true;
+        self
+    }
+
+    /// If this setting is used, sets up a new IPC namespace
+    /// for the executed processes. Each IPC namespace has its own set of
+    /// System V IPC identifiers and its own POSIX message queue file
+    /// system. This is useful to avoid name clash of IPC identifiers.
+    ///
+    /// Read `PrivateIPC=` in [systemd.exec(5)](man:systemd.exec(5)) for
+    /// details.
+    ///
+    /// This setting is not available if the feature `systemd_249` is
+    /// disabled.  And, it will be ignored silently if `CONFIG_IPC_NS` is
+    /// not enabled in the configuration of the running kernel.
+    #[cfg(feature = "systemd_248")]
+    pub fn private_ipc(mut self) -> Self {
+        self.private_ipc = true;
+        self
+    }
+
+    /// Set up a mount point for the transient service.  See [Mount] for
+    /// details.
+    ///
+    /// This setting is not available if the feature `systemd_233` is
+    /// disabled.
+    #[cfg(feature = "systemd_233")]
     pub fn mount<T: AsRef<str>>(mut self, mount_point: T, mount: Mount) -> Self {
         self.mount.push((mount_point.as_ref().to_owned(), mount));
         self
     }
 
+    /// Mount the OS directories read-only for the transient service.  See
+    /// [ProtectSystem] for details.
+    ///
+    /// This setting is unavailable with the feature `systemd_236`
+    /// disabled.
+    #[cfg(feature = "systemd_236")]
+    pub fn protect_system(self, x: ProtectSystem) -> Self {
+        Self {
+            protect_system: Some(x),
+            ..self
+        }
+    }
+
+    /// Restrict the access to the home directories for the transient
+    /// service.  See [ProtectHome] for details.
+    ///
+    /// This setting is unavailable with the feature `systemd_236`
+    /// disabled.
+    #[cfg(feature = "systemd_236")]
+    pub fn protect_home(self, x: ProtectHome) -> Self {
+        Self {
+            protect_home: Some(x),
+            ..self
+        }
+    }
+
+    /// Make the paths read-only for the executed processes.  It can be
+    /// called multiple times to add more paths.  See [AccessPath] for the
+    /// path prefixes.
+    ///
+    /// Read `ReadOnlyPaths=` in [systemd.exec(5)](man:systemd.exec(5)) for
+    /// details.
+    ///
+    /// This setting is unavailable with the feature `systemd_236`
+    /// disabled.
+    #[cfg(feature = "systemd_236")]
+    pub fn read_only_paths<I, P>(mut self, paths: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<AccessPath>,
+    {
+        self.paths
+            .read_only
+            .extend(paths.into_iter().map(Into::into));
+        self
+    }
+
+    /// Like [Self::read_only_paths], but make the paths writable, for
+    /// example the output directory under [ProtectSystem::Strict].
+    ///
+    /// Read `ReadWritePaths=` in [systemd.exec(5)](man:systemd.exec(5))
+    /// for details.
+    ///
+    /// This setting is unavailable with the feature `systemd_236`
+    /// disabled.
+    #[cfg(feature = "systemd_236")]
+    pub fn read_write_paths<I, P>(mut self, paths: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<AccessPath>,
+    {
+        self.paths
+            .read_write
+            .extend(paths.into_iter().map(Into::into));
+        self
+    }
+
+    /// Like [Self::read_only_paths], but make the paths inaccessible.
+    ///
+    /// Read `InaccessiblePaths=` in
+    /// [systemd.exec(5)](man:systemd.exec(5)) for details.
+    ///
+    /// This setting is unavailable with the feature `systemd_236`
+    /// disabled.
+    #[cfg(feature = "systemd_236")]
+    pub fn inaccessible_paths<I, P>(mut self, paths: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<AccessPath>,
+    {
+        self.paths
+            .inaccessible
+            .extend(paths.into_iter().map(Into::into));
+        self
+    }
+
+    /// Like [Self::read_only_paths], but allow executing programs from the
+    /// paths even if they are in [Self::no_exec_paths].
+    ///
+    /// Read `ExecPaths=` in [systemd.exec(5)](man:systemd.exec(5)) for
+    /// details.
+    ///
+    /// This setting is unavailable with the feature `systemd_247`
+    /// disabled.
+    #[cfg(feature = "systemd_247")]
+    pub fn exec_paths<I, P>(mut self, paths: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<AccessPath>,
+    {
+        self.paths.exec.extend(paths.into_iter().map(Into::into));
+        self
+    }
+
+    /// Like [Self::read_only_paths], but forbid executing programs from the
+    /// paths.  For example, only allow the programs in `/usr` with `/` in
+    /// this list and `/usr` in [Self::exec_paths].
+    ///
+    /// Read `NoExecPaths=` in [systemd.exec(5)](man:systemd.exec(5)) for
+    /// details.
+    ///
+    /// This setting is unavailable with the feature `systemd_247`
+    /// disabled.
+    #[cfg(feature = "systemd_247")]
+    pub fn no_exec_paths<I, P>(mut self, paths: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<AccessPath>,
+    {
+        self.paths.no_exec.extend(paths.into_iter().map(Into::into));
+        self
+    }
+
     /// Mount the API file systems `/proc`, `/sys`, `/dev`, and `/run`
     /// for the private mount namespace of the transient service.
     ///
@@ -759,6 +1820,125 @@ impl RunSystem {
         }
     }
 
+    /// Restrict the system calls of the executed processes with seccomp.
+    /// See [SystemCallFilter] for details.
+    ///
+    /// This setting is unavailable with the feature `systemd_236`
+    /// disabled.
+    #[cfg(feature = "systemd_236")]
+    pub fn system_call_filter(self, filter: SystemCallFilter) -> Self {
+        Self {
+            syscall_filter: Some(filter),
+            ..self
+        }
+    }
+
+    /// Limit the capabilities the executed processes may ever gain to
+    /// `caps`.  For example, an empty list drops all capabilities, even
+    /// if the processes are running as root.
+    ///
+    /// Read `CapabilityBoundingSet=` in
+    /// [systemd.exec(5)](man:systemd.exec(5)) for details.
+    ///
+    /// This setting is unavailable with the feature `systemd_236`
+    /// disabled.
+    #[cfg(feature = "systemd_236")]
+    pub fn capability_bounding_set<I>(self, caps: I) -> Self
+    where
+        I: IntoIterator<Item = Capability>,
+    {
+        Self {
+            capability_bounding_set: Some(capability::mask(caps, false)),
+            ..self
+        }
+    }
+
+    /// Like [Self::capability_bounding_set], but remove `caps` from the
+    /// capability bounding set, and keep the others.
+    ///
+    /// This setting is unavailable with the feature `systemd_236`
+    /// disabled.
+    #[cfg(feature = "systemd_236")]
+    pub fn capability_bounding_set_deny<I>(self, caps: I) -> Self
+    where
+        I: IntoIterator<Item = Capability>,
+    {
+        Self {
+            capability_bounding_set: Some(capability::mask(caps, true)),
+            ..self
+        }
+    }
+
+    /// Grant `caps` as ambient capabilities to the executed processes,
+    /// so a process running as a non-root [Identity] can still have them.
+    /// For example, [Capability::SysPtrace] for a process inspecting
+    /// other processes.
+    ///
+    /// Read `AmbientCapabilities=` in
+    /// [systemd.exec(5)](man:systemd.exec(5)) for details.
+    ///
+    /// This setting is unavailable with the feature `systemd_236`
+    /// disabled.
+    #[cfg(feature = "systemd_236")]
+    pub fn ambient_capabilities<I>(self, caps: I) -> Self
+    where
+        I: IntoIterator<Item = Capability>,
+    {
+        Self {
+            ambient_capabilities: Some(capability::mask(caps, false)),
+            ..self
+        }
+    }
+
+    /// Like [Self::ambient_capabilities], but grant all capabilities
+    /// except `caps`.
+    ///
+    /// This setting is unavailable with the feature `systemd_236`
+    /// disabled.
+    #[cfg(feature = "systemd_236")]
+    pub fn ambient_capabilities_deny<I>(self, caps: I) -> Self
+    where
+        I: IntoIterator<Item = Capability>,
+    {
+        Self {
+            ambient_capabilities: Some(capability::mask(caps, true)),
+            ..self
+        }
+    }
+
+    /// Set the secure bits of the executed processes, see [SecureBit].
+    ///
+    /// This setting is unavailable with the feature `systemd_236`
+    /// disabled.
+    #[cfg(feature = "systemd_236")]
+    pub fn secure_bits<I: IntoIterator<Item = SecureBit>>(self, bits: I) -> Self {
+        Self {
+            secure_bits: Some(capability::secure_bits(bits)),
+            ..self
+        }
+    }
+
+    /// Set soft and hard limits of a resource.  The unit of the values
+    /// depends on the resource, read [Rlimit] for details.  Setting the
+    /// same resource again overrides the previous setting.
+    ///
+    /// If `soft` is greater than `hard`, it will be trimmed to `hard`
+    /// silently.
+    ///
+    /// Read `LimitCPU=` etc. in [systemd.exec(5)](man:systemd.exec(5)) and
+    /// [prlimit(2)](man:prlimit(2)) for details.
+    pub fn limit<S, H>(mut self, resource: Rlimit, soft: S, hard: H) -> Self
+    where
+        S: Into<RlimitValue>,
+        H: Into<RlimitValue>,
+    {
+        let hard = hard.into();
+        let soft = std::cmp::min(soft.into(), hard);
+        self.rlimits.retain(|(r, _, _)| *r != resource);
+        self.rlimits.push((resource, soft, hard));
+        self
+    }
+
     /// Set soft and hard limits of the maximum size in bytes of files that
     /// the process may create.
     ///
@@ -769,12 +1949,7 @@ impl RunSystem {
     /// Read `LimitFSIZE=` in [systemd.exec(5)](man:systemd.exec(5)) and
     /// `RLIMIT_FSIZE` in [prlimit(2)](man:prlimit(2)) for details.
     pub fn limit_fsize_soft_hard(self, soft: Byte, hard: Byte) -> Self {
-        let soft = std::cmp::min(soft, hard);
-        Self {
-            limit_fsize: Some(hard),
-            limit_fsize_soft: Some(soft),
-            ..self
-        }
+        self.limit(Rlimit::Fsize, soft, hard)
     }
 
     /// Shorthand for `self.limit_fsize_soft_hard(lim, lim)`.
@@ -792,12 +1967,7 @@ impl RunSystem {
     /// Read `LimitCORE=` in [systemd.exec(5)](man:systemd.exec(5)) and
     /// `RLIMIT_CORE` in [prlimit(2)](man:prlimit(2)) for details.
     pub fn limit_core_soft_hard(self, soft: Byte, hard: Byte) -> Self {
-        let soft = std::cmp::min(soft, hard);
-        Self {
-            limit_core: Some(hard),
-            limit_core_soft: Some(soft),
-            ..self
-        }
+        self.limit(Rlimit::Core, soft, hard)
     }
 
     /// Shorthand for `self.limit_fsize_soft_hard(lim, lim)`.
@@ -814,12 +1984,7 @@ impl RunSystem {
     /// Read `LimitNPROC=` in [systemd.exec(5)](man:systemd.exec(5)) and
     /// `RLIMIT_NPROC` in [prlimit(2)](man:prlimit(2)) for details.
     pub fn limit_nproc_soft_hard(self, soft: NonZeroU64, hard: NonZeroU64) -> Self {
-        let soft = std::cmp::min(soft, hard);
-        Self {
-            limit_nproc: Some(hard.into()),
-            limit_nproc_soft: Some(soft.into()),
-            ..self
-        }
+        self.limit(Rlimit::Nproc, soft.get(), hard.get())
     }
 
     /// Shorthand for `self.limit_nproc_soft_hard(lim, lim)`.
@@ -836,12 +2001,7 @@ impl RunSystem {
     /// Read `LimitNOFILE=` in [systemd.exec(5)](man:systemd.exec(5)) and
     /// `RLIMIT_NOFILE` in [prlimit(2)](man:prlimit(2)) for details.
     pub fn limit_nofile_soft_hard(self, soft: NonZeroU64, hard: NonZeroU64) -> Self {
-        let soft = std::cmp::min(soft, hard);
-        Self {
-            limit_nofile: Some(hard.into()),
-            limit_nofile_soft: Some(soft.into()),
-            ..self
-        }
+        self.limit(Rlimit::Nofile, soft.get(), hard.get())
     }
 
     /// Shorthand for `self.limit_nofile_soft_hard(lim, lim)`.
@@ -857,12 +2017,7 @@ impl RunSystem {
     /// Read `LimitSTACK=` in [systemd.exec(5)](man:systemd.exec(5)) and
     /// `RLIMIT_STACK` in [prlimit(2)](man:prlimit(2)) for details.
     pub fn limit_stack_soft_hard(self, soft: Byte, hard: Byte) -> Self {
-        let soft = std::cmp::min(soft, hard);
-        Self {
-            limit_stack: Some(hard),
-            limit_stack_soft: Some(soft),
-            ..self
-        }
+        self.limit(Rlimit::Stack, soft, hard)
     }
 
     /// Shorthand for `self.limit_stack_soft_hard(lim, lim)`.
@@ -884,45 +2039,198 @@ impl RunSystem {
         }
     }
 
-    /// Controls where file descriptor 1 (STDOUT) of the executed processes
-    /// is connected to.
+    /// Controls where file descriptor 1 (STDOUT) of the executed processes
+    /// is connected to.
+    ///
+    /// Read [OutputSpec] and `StandardOutput=` in
+    /// [systemd.exec(5)](man:systemd.exec(5)) for details.
+    ///
+    /// The default depends on system configuration.
+    pub fn stdout(self, spec: OutputSpec) -> Self {
+        Self {
+            stdout: Some(spec),
+            ..self
+        }
+    }
+
+    /// Controls where file descriptor 2 (STDERR) of the executed processes
+    /// is connected to.
+    ///
+    /// Read [OutputSpec] and `StandardError=` in
+    /// [systemd.exec(5)](man:systemd.exec(5)) for details.
+    ///
+    /// The default depends on system configuration.
+    pub fn stderr(self, spec: OutputSpec) -> Self {
+        Self {
+            stderr: Some(spec),
+            ..self
+        }
+    }
+
+    /// Connect the input and outputs of the executed processes to a newly
+    /// allocated pseudo-terminal, like `systemd-run --pty`.  The
+    /// controller side can be taken with [StartedRun::take_pty] once the
+    /// transient service is started.
+    ///
+    /// The replica side is the controlling terminal of the main process,
+    /// opened by the service manager with `StandardInput=tty` and
+    /// `TTYPath=`.  It's also passed to the service manager through D-Bus
+    /// for the outputs, so the D-Bus connection must support passing UNIX
+    /// file descriptors.
+    ///
+    /// This overrides [Self::stdin], [Self::stdout], and [Self::stderr].
+    /// Read `StandardInput=` and `TTYPath=` in
+    /// [systemd.exec(5)](man:systemd.exec(5)) for details.
+    ///
+    /// This setting is unavailable with the feature `systemd_236`
+    /// disabled.
+    #[cfg(feature = "systemd_236")]
+    pub fn pty(self) -> Self {
+        Self { pty: true, ..self }
+    }
+
+    /// Sets the working directory for executed processes.
+    ///
+    /// Read `WorkingDirectory=` in
+    /// [systemd.exec(5)](man:systemd.exec(5)) for details.
+    ///
+    /// This setting is unavailable with the feature `systemd_227`
+    /// disabled.
+    #[cfg(feature = "systemd_227")]
+    pub fn current_dir<P: AsRef<str>>(self, path: P) -> Self {
+        Self {
+            current_dir: Some(path.as_ref().to_owned()),
+            ..self
+        }
+    }
+
+    /// Set an environment variable for executed processes.  If the
+    /// variable is set multiple times, the last value is used.
+    ///
+    /// The name of the variable must not be empty and must not contain
+    /// `=` or NUL, and the value must not contain NUL.  Or
+    /// [Error::InvalidEnvironment] will be returned when starting the
+    /// transient service.
+    ///
+    /// Read `Environment=` in [systemd.exec(5)](man:systemd.exec(5)) for
+    /// details.
+    pub fn env<K: AsRef<str>, V: AsRef<str>>(mut self, key: K, value: V) -> Self {
+        self.env.set(key.as_ref(), value.as_ref());
+        self
+    }
+
+    /// Set multiple environment variables for executed processes, like
+    /// calling [Self::env] for each of them.
+    pub fn envs<I, K, V>(mut self, vars: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        for (k, v) in vars {
+            self.env.set(k.as_ref(), v.as_ref());
+        }
+        self
+    }
+
+    /// Remove an environment variable for executed processes, even if it
+    /// is set by the service manager or an environment file.
+    ///
+    /// Read `UnsetEnvironment=` in [systemd.exec(5)](man:systemd.exec(5))
+    /// for details.
+    ///
+    /// This setting is unavailable with the feature `systemd_236`
+    /// disabled.
+    #[cfg(feature = "systemd_236")]
+    pub fn env_remove<K: AsRef<str>>(mut self, key: K) -> Self {
+        self.env.remove(key.as_ref());
+        self
+    }
+
+    /// Read environment variables for executed processes from a file.
+    /// The file must exist when the transient service is started.
+    ///
+    /// Read `EnvironmentFile=` in [systemd.exec(5)](man:systemd.exec(5))
+    /// for details.
+    ///
+    /// This setting is unavailable with the feature `systemd_236`
+    /// disabled.
+    #[cfg(feature = "systemd_236")]
+    pub fn env_file<P: AsRef<str>>(mut self, path: P) -> Self {
+        self.env.add_file(path.as_ref());
+        self
+    }
+
+    /// Pass environment variables from the service manager to executed
+    /// processes.  The variables not set for the service manager are
+    /// ignored.
+    ///
+    /// Read `PassEnvironment=` in [systemd.exec(5)](man:systemd.exec(5))
+    /// for details.
+    ///
+    /// This setting is unavailable with the feature `systemd_229`
+    /// disabled.
+    #[cfg(feature = "systemd_229")]
+    pub fn pass_environment<I: IntoIterator<Item = T>, T: AsRef<str>>(mut self, names: I) -> Self {
+        for k in names {
+            self.env.pass(k.as_ref());
+        }
+        self
+    }
+
+    /// Sets the process name ("syslog tag") to prefix log lines sent to
+    /// the logging system or the kernel log buffer with.  The default is
+    /// the process name of the executed process.
     ///
-    /// Read [OutputSpec] and `StandardOutput=` in
-    /// [systemd.exec(5)](man:systemd.exec(5)) for details.
+    /// Read `SyslogIdentifier=` in [systemd.exec(5)](man:systemd.exec(5))
+    /// for details.
     ///
-    /// The default depends on system configuration.
-    pub fn stdout(self, spec: OutputSpec) -> Self {
+    /// This setting is unavailable with the feature `systemd_231`
+    /// disabled.
+    #[cfg(feature = "systemd_231")]
+    pub fn syslog_identifier<S: AsRef<str>>(self, id: S) -> Self {
         Self {
-            stdout: Some(spec),
+            syslog_identifier: Some(id.as_ref().to_owned()),
             ..self
         }
     }
 
-    /// Controls where file descriptor 2 (STDERR) of the executed processes
-    /// is connected to.
+    /// Add extra fields to all log records written by the executed
+    /// processes.  The field names must consist of uppercase letters,
+    /// digits, and underscores, and must not begin with an underscore or
+    /// a digit.  Use [FinishedRun::journal] and [JournalEntry::field] to
+    /// read them back.
     ///
-    /// Read [OutputSpec] and `StandardError=` in
-    /// [systemd.exec(5)](man:systemd.exec(5)) for details.
+    /// Read `LogExtraFields=` in [systemd.exec(5)](man:systemd.exec(5))
+    /// for details.
     ///
-    /// The default depends on system configuration.
-    pub fn stderr(self, spec: OutputSpec) -> Self {
-        Self {
-            stderr: Some(spec),
-            ..self
+    /// This setting is unavailable with the feature `systemd_236`
+    /// disabled.
+    #[cfg(feature = "systemd_236")]
+    pub fn log_extra_fields<I, K, V>(mut self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        for (k, v) in fields {
+            let field = (k.as_ref().to_owned(), v.as_ref().to_owned());
+            self.log_extra_fields.push(field);
         }
+        self
     }
 
-    /// Sets the working directory for executed processes.
+    /// Drop the log messages with a lower level than the [LogLevel].
     ///
-    /// Read `WorkingDirectory=` in
-    /// [systemd.exec(5)](man:systemd.exec(5)) for details.
+    /// Read `LogLevelMax=` in [systemd.exec(5)](man:systemd.exec(5)) for
+    /// details.
     ///
-    /// This setting is unavailable with the feature `systemd_227`
+    /// This setting is unavailable with the feature `systemd_236`
     /// disabled.
-    #[cfg(feature = "systemd_227")]
-    pub fn current_dir<P: AsRef<str>>(self, path: P) -> Self {
+    #[cfg(feature = "systemd_236")]
+    pub fn log_level_max(self, level: LogLevel) -> Self {
         Self {
-            current_dir: Some(path.as_ref().to_owned()),
+            log_level_max: Some(level),
             ..self
         }
     }
@@ -993,6 +2301,15 @@ impl RunSystem {
         Self { cpu_sched, ..self }
     }
 
+    /// Specify IO scheduling class and priority.
+    /// See [IoScheduling] for details.
+    pub fn io_schedule(self, io_sched: IoScheduling) -> Self {
+        Self {
+            io_sched: Some(io_sched),
+            ..self
+        }
+    }
+
     /// See the same `/tmp/`, `/var/tmp/`, IPC namespace, and network
     /// namespace as one unit that is already started and specified with
     /// this setting.  If this setting is used multiple times and the
@@ -1014,7 +2331,58 @@ impl RunSystem {
     }
 
     /// Start the transient service.
-    pub async fn start<'a>(mut self) -> Result<StartedRun<'a>> {
+    ///
+    /// A new D-Bus connection is opened for the transient service, to the
+    /// system bus, or the session bus if the [Identity] is the session
+    /// (for [RunUser]).  Use [Manager::start] to reuse a connection for
+    /// many transient services.
+    pub async fn start<'a>(self) -> Result<StartedRun<'a>> {
+        self.validate(true)?;
+        let manager = if identity::is_session(&self.identity) {
+            Manager::user().await
+        } else {
+            Manager::system().await
+        }?;
+        self.start_with(&manager).await
+    }
+
+    // Check the settings which can't be checked in the setters, before
+    // anything is sent over D-Bus.  The checks against the local host are
+    // skipped unless `local` is true, see manager::is_local.
+    fn validate(&self, local: bool) -> Result<()> {
+        self.env.validate()?;
+        if let Some(x) = &self.numa_policy {
+            cpuset::check_numa_policy(x)?;
+        }
+        let numa_nodes = self.numa_policy.as_ref().and_then(cpuset::numa_nodes);
+        let cpus = [&self.allowed_cpus, &self.cpu_affinity];
+        let nodes = [Some(&self.allowed_memory_nodes), numa_nodes];
+        for x in cpus.into_iter().chain(nodes.into_iter().flatten()) {
+            cpuset::check_range(x)?;
+        }
+        if local {
+            for x in cpus {
+                cpuset::check_online(x)?;
+            }
+            for x in nodes.into_iter().flatten() {
+                cpuset::check_nodes_online(x)?;
+            }
+        }
+        for (k, _) in &self.log_extra_fields {
+            if !journal::is_valid_field_name(k) {
+                return Err(Error::InvalidLogField(k.clone()));
+            }
+        }
+        if let Some(x) = self.paths.check() {
+            return Err(Error::InvalidPath(x.to_owned()));
+        }
+        if let Some(x) = self.syscall_filter.as_ref().and_then(seccomp::check) {
+            return Err(Error::InvalidSystemCall(x.to_owned()));
+        }
+        Ok(())
+    }
+
+    async fn start_with<'a>(mut self, manager: &Manager) -> Result<StartedRun<'a>> {
         let mut argv = vec![&self.path];
         argv.extend(&self.args);
 
@@ -1034,12 +2402,28 @@ impl RunSystem {
         for (k, v) in [
             ("WorkingDirectory", self.current_dir),
             ("Slice", self.slice),
+            ("SyslogIdentifier", self.syslog_identifier),
         ] {
             if let Some(v) = v {
                 properties.push((k, Value::from(v)));
             }
         }
 
+        properties.extend(self.env.marshal());
+
+        if !self.log_extra_fields.is_empty() {
+            let v = self
+                .log_extra_fields
+                .into_iter()
+                .map(|(k, v)| (k + "=" + &v).into_bytes())
+                .collect::<Vec<_>>();
+            properties.push(("LogExtraFields", Value::from(v)));
+        }
+
+        if let Some(v) = self.log_level_max {
+            properties.push(("LogLevelMax", Value::from(v.priority())));
+        }
+
         let join_ns = self.joins_namespace_of;
         if !join_ns.is_empty() {
             properties.push(("JoinsNamespaceOf", Value::from(join_ns)));
@@ -1056,12 +2440,23 @@ impl RunSystem {
             properties.push(("ProtectProc", Value::from(v)));
         }
 
+        if let Some(x) = self.protect_system {
+            properties.push(("ProtectSystem", Value::from(protect::marshal_system(x))));
+        }
+
+        if let Some(x) = self.protect_home {
+            properties.push(("ProtectHome", Value::from(protect::marshal_home(x))));
+        }
+
+        properties.extend(self.paths.marshal());
+
         let identity_prop = identity::unit_properties(&self.identity);
         properties.extend(identity_prop);
 
         for (k, v) in [
             ("RuntimeMaxUSec", &self.runtime_max),
             ("TimeoutStopUSec", &self.timeout_stop),
+            ("CPUQuotaPeriodUSec", &self.cpu_quota_period),
         ] {
             if let Some(d) = v {
                 let usec = u64::try_from(d.as_micros()).unwrap_or(u64::MAX);
@@ -1069,26 +2464,27 @@ impl RunSystem {
             }
         }
 
-        if !self.allowed_cpus.is_empty() {
-            let mut cpu_set = vec![];
-            for &cpu in &self.allowed_cpus {
-                let (x, y) = (cpu / 8, cpu % 8);
-                if cpu_set.len() <= x {
-                    cpu_set.resize(x + 1, 0u8);
-                }
-                cpu_set[x] |= 1 << y;
+        for (k, v) in [
+            ("AllowedCPUs", &self.allowed_cpus),
+            ("CPUAffinity", &self.cpu_affinity),
+            ("AllowedMemoryNodes", &self.allowed_memory_nodes),
+        ] {
+            if !v.is_empty() {
+                properties.push((k, Value::from(cpuset::mask(v))));
             }
-            properties.push(("AllowedCPUs", Value::from(cpu_set)));
         }
 
-        for (k, v) in [
-            ("LimitNPROC", &self.limit_nproc),
-            ("LimitNPROCSoft", &self.limit_nproc_soft),
-            ("LimitNOFILE", &self.limit_nofile),
-            ("LimitNOFILESoft", &self.limit_nofile_soft),
-        ] {
-            if let Some(v) = v {
-                properties.push((k, Value::from(v)))
+        if let Some(p) = self.numa_policy {
+            let (policy, mask) = cpuset::marshal_numa(p);
+            properties.push(("NUMAPolicy", Value::from(policy)));
+            if let Some(mask) = mask {
+                properties.push(("NUMAMask", Value::from(mask)));
+            }
+        }
+
+        for &(r, soft, hard) in &self.rlimits {
+            for (k, v) in rlimit::marshal(r, soft, hard) {
+                properties.push((k, Value::from(v)));
             }
         }
 
@@ -1099,23 +2495,44 @@ impl RunSystem {
         };
 
         for (k, v) in [
-            (memory_max_name, &self.memory_max),
-            ("MemorySwapMax", &self.memory_swap_max),
-            ("LimitFSIZE", &self.limit_fsize),
-            ("LimitFSIZESoft", &self.limit_fsize_soft),
-            ("LimitSTACK", &self.limit_stack),
-            ("LimitSTACKSoft", &self.limit_stack_soft),
-            ("LimitCORE", &self.limit_core),
-            ("LimitCORESoft", &self.limit_core_soft),
+            ((memory_max_name, "MemoryMaxScale"), self.memory_max),
+            (
+                ("MemorySwapMax", "MemorySwapMaxScale"),
+                self.memory_swap_max,
+            ),
+            (("MemoryHigh", "MemoryHighScale"), self.memory_high),
+            (("MemoryLow", "MemoryLowScale"), self.memory_low),
+            (("MemoryMin", "MemoryMinScale"), self.memory_min),
+            (
+                ("MemoryZSwapMax", "MemoryZSwapMaxScale"),
+                self.memory_zswap_max,
+            ),
         ] {
             if let Some(v) = v {
-                properties.push((k, Value::from(v.as_u64())))
+                properties.push(memory::marshal(k, v));
+            }
+        }
+
+        properties.extend(memory::marshal_oom(self.oom));
+
+        match self.tasks_max {
+            Some(TasksMax::Count(n)) => properties.push(("TasksMax", Value::from(n))),
+            Some(TasksMax::Percent(x)) => {
+                let v = Value::from(memory::scale_from_percent(x));
+                properties.push(("TasksMaxScale", v));
             }
+            None => {}
         }
+        properties.extend(self.io.marshal());
 
-        if let Some(v) = self.cpu_quota {
-            let v = std::cmp::min(v, u64::MAX / 10000);
-            properties.push(("CPUQuotaPerSecUSec", Value::from(v * 10000)));
+        for (k, v) in [
+            ("CPUQuotaPerSecUSec", self.cpu_quota),
+            ("CPUWeight", self.cpu_weight),
+            ("StartupCPUWeight", self.startup_cpu_weight),
+        ] {
+            if let Some(v) = v {
+                properties.push((k, Value::from(v)));
+            }
         }
 
         for (k, v) in [
@@ -1132,6 +2549,24 @@ impl RunSystem {
             }
         }
 
+        let system_call_filter_kills = self.syscall_filter.as_ref().is_some_and(seccomp::kills);
+        if let Some(f) = self.syscall_filter {
+            properties.extend(seccomp::marshal(f));
+        }
+
+        for (k, v) in [
+            ("CapabilityBoundingSet", self.capability_bounding_set),
+            ("AmbientCapabilities", self.ambient_capabilities),
+        ] {
+            if let Some(v) = v {
+                properties.push((k, Value::from(v)));
+            }
+        }
+
+        if let Some(v) = self.secure_bits {
+            properties.push(("SecureBits", Value::from(v)));
+        }
+
         let mut p_bind = vec![];
         let mut p_bind_ro = vec![];
         let mut p_image = vec![];
@@ -1162,28 +2597,39 @@ impl RunSystem {
             properties.push(("TemporaryFileSystem", Value::from(p_tmpfs)));
         }
 
-        let mut io_prop = vec![];
+        let mut pty = None;
+        if self.pty {
+            let (controller, replica, path) = pty::open().map_err(Error::CreatePtyFail)?;
+            let dup = |fd: &std::os::fd::OwnedFd| fd.try_clone().map_err(Error::CreatePtyFail);
+            // The service manager opens TTYPath= for StandardInput=tty and
+            // makes it the controlling terminal.  The outputs are passed
+            // as file descriptors, so the replica is kept open until the
+            // unit is stopped, even if the main process has not opened
+            // it yet.
+            self.stdin = Some(ioredirect::input_tty());
+            self.stdout = Some(ioredirect::output_fd(dup(&replica)?));
+            self.stderr = Some(ioredirect::output_fd(replica));
+            properties.push(("TTYPath", Value::from(path)));
+            pty = Some(controller);
+        }
 
-        for (pfx, (sfx, val)) in [
-            ("StandardInput", self.stdin.map(ioredirect::marshal_input)),
-            (
-                "StandardOutput",
-                self.stdout.map(ioredirect::marshal_output),
-            ),
-            ("StandardError", self.stderr.map(ioredirect::marshal_output)),
+        let (stdin, stdin_pipe) =
+            ioredirect::marshal_input(self.stdin).map_err(Error::CreatePipeFail)?;
+        let (stdout, stdout_pipe) =
+            ioredirect::marshal_output(self.stdout).map_err(Error::CreatePipeFail)?;
+        let (stderr, stderr_pipe) =
+            ioredirect::marshal_output(self.stderr).map_err(Error::CreatePipeFail)?;
+
+        let io_prop = [
+            ("StandardInput", stdin),
+            ("StandardOutput", stdout),
+            ("StandardError", stderr),
         ]
         .into_iter()
-        .filter_map(|(a, b)| Some(a).zip(b))
-        {
-            let key = pfx.to_owned() + sfx;
-            io_prop.push((key, val))
-        }
-
-        for (k, v) in io_prop.iter() {
-            properties.push((k, Value::from(v)))
-        }
+        .flat_map(|(pfx, x)| x.into_iter().map(move |(sfx, v)| (pfx.to_owned() + sfx, v)))
+        .collect::<Vec<_>>();
 
-        let (policy, priority, reset_on_fork) = cpu_sched::marshal(self.cpu_sched);
+        let (policy, priority, reset_on_fork, nice) = cpu_sched::marshal(self.cpu_sched);
 
         for (k, v) in [
             ("CPUSchedulingPolicy", Value::from(policy)),
@@ -1196,16 +2642,57 @@ impl RunSystem {
             properties.push(("CPUSchedulingPriority", Value::from(v)));
         }
 
-        let properties = properties.iter().map(|(x, y)| (*x, y)).collect::<Vec<_>>();
+        if let Some(v) = nice {
+            properties.push(("Nice", Value::from(v)));
+        }
+
+        if let Some(x) = self.io_sched {
+            let (class, priority) = io_sched::marshal(x);
+            properties.push(("IOSchedulingClass", Value::from(class)));
+            if let Some(v) = priority {
+                properties.push(("IOSchedulingPriority", Value::from(v)));
+            }
+        }
 
-        let bus = if identity::is_session(&self.identity) {
-            Connection::session().await
-        } else {
-            Connection::system().await
+        // Needed for reporting the resource usage in FinishedRun and
+        // StartedRun::sample.
+        for (k, v) in [
+            ("CPUAccounting", cfg!(feature = "systemd_227")),
+            ("MemoryAccounting", cfg!(feature = "systemd_227")),
+            ("TasksAccounting", cfg!(feature = "systemd_227")),
+            (
+                "IOAccounting",
+                cfg!(all(feature = "unified_cgroup", feature = "systemd_231")),
+            ),
+        ] {
+            if v {
+                properties.push((k, Value::from(true)));
+            }
         }
-        .map_err(Error::DBusConnectionFail)?;
+
+        let properties = properties
+            .iter()
+            .map(|(x, y)| (*x, y))
+            .chain(io_prop.iter().map(|(x, y)| (x.as_str(), y)))
+            .collect::<Vec<_>>();
+
+        let rlimit = |x| self.rlimits.iter().find(|(r, _, _)| *r == x);
+        let limits = verdict::Limits {
+            limit_fsize_soft: match rlimit(Rlimit::Fsize) {
+                Some((_, RlimitValue::Value(x), _)) => Some(Byte::from(*x)),
+                _ => None,
+            },
+            limit_cpu: match rlimit(Rlimit::Cpu) {
+                Some((_, _, RlimitValue::Value(x))) => Some(Duration::from_secs(*x)),
+                _ => None,
+            },
+            system_call_filter_kills,
+        };
+
+        let bus = manager::bus(manager);
         if self.service_name.is_none() {
-            self.service_name = Some(default_unit_name(&bus)?);
+            let seq = manager::next_seq(manager);
+            self.service_name = Some(default_unit_name(bus, seq)?);
         }
         let unit_name = self.service_name.as_ref().unwrap();
         let unit_path = object_path_from_unit_name(unit_name)?;
@@ -1214,45 +2701,319 @@ impl RunSystem {
         // service.  Or we may miss D-Bus signals, causing StartedRun::wait
         // to hang forever.  And this also prevents the start of the
         // transient service in case this fails.
-        let (proxy, stream) = listen_unit_property_change(&bus, &unit_path).await?;
+        let (proxy, stream) = listen_unit_property_change(bus, &unit_path).await?;
 
-        sd::SystemdManagerProxy::builder(&bus)
-            .build()
-            .await
-            .expect("should not fail with hardcoded parameters in sd.rs")
+        let local = manager::is_local(manager);
+        let manager = manager::proxy(manager).clone();
+        manager
             .start_transient_unit(unit_name, "fail", &properties, &[])
             .await
-            .map_err(Error::StartFail)
-            .map(|_| StartedRun { stream, proxy })
+            .map_err(Error::StartFail)?;
+        Ok(StartedRun {
+            stream,
+            proxy,
+            manager,
+            unit_name: unit_name.clone(),
+            has_job: false,
+            active_state: None,
+            pending_events: VecDeque::new(),
+            limits,
+            on_drop: OnDrop::Nothing,
+            stdin: stdin_pipe,
+            stdout: stdout_pipe,
+            stderr: stderr_pipe,
+            pty,
+            user: identity::is_session(&self.identity),
+            local,
+            sampled: Mutex::new(cgroup::Stat::default()),
+        })
     }
 }
 
-impl StartedRun<'_> {
+impl<'a> StartedRun<'a> {
     /// Wait until a [StartedRun] is finished.
-    pub async fn wait(self) -> Result<FinishedRun> {
-        let mut stream = self.stream;
+    pub async fn wait(mut self) -> Result<FinishedRun> {
+        self.wait_inactive().await?;
+        self.finish().await
+    }
+
+    /// Like [Self::wait], but stop the transient service with
+    /// [Self::stop] if it's not finished in the duration `d`, and then
+    /// wait until it's stopped.
+    ///
+    /// This is intended for an outer deadline, for example aborting the
+    /// runs when a contest is cancelled.  Use [RunSystem::runtime_max] to
+    /// limit the running time of the service itself.  Use
+    /// [FinishedRun::is_aborted] to tell if the transient service has
+    /// been stopped by this.
+    pub async fn wait_timeout(mut self, d: Duration) -> Result<FinishedRun> {
+        use futures::future::{select, Either};
+        let finished = {
+            let wait = self.wait_inactive();
+            futures::pin_mut!(wait);
+            match select(wait, async_io::Timer::after(d)).await {
+                Either::Left((r, _)) => r.map(|_| true)?,
+                Either::Right(_) => false,
+            }
+        };
+        if !finished {
+            self.stop().await?;
+            self.wait_inactive().await?;
+        }
+        let mut r = self.finish().await?;
+        r.aborted = !finished;
+        Ok(r)
+    }
+
+    /// Stop the transient service, as `systemctl stop` does.  The
+    /// processes are killed as configured with `KillMode=` and
+    /// [RunSystem::timeout_stop].
+    ///
+    /// This only enqueues a stop job.  Use [Self::wait] to wait until the
+    /// transient service is really stopped.
+    pub async fn stop(&self) -> Result<()> {
+        self.manager
+            .stop_unit(&self.unit_name, "replace")
+            .await
+            .map_err(Error::StopFail)
+            .map(|_| ())
+    }
+
+    /// Send the signal `signal` to the processes of the transient service
+    /// specified by `who`, as `systemctl kill` does.
+    ///
+    /// Read `KillUnit()` in
+    /// [org.freedesktop.systemd1(5)](man:org.freedesktop.systemd1(5)) for
+    /// details.
+    pub async fn kill(&self, signal: i32, who: KillWho) -> Result<()> {
+        self.manager
+            .kill_unit(&self.unit_name, who.as_str(), signal)
+            .await
+            .map_err(Error::KillFail)
+    }
+
+    /// Take the write end of the pipe connected to the input of the
+    /// transient service, if [InputSpec::pipe] is used.
+    pub fn take_stdin(&mut self) -> Option<InputPipe> {
+        self.stdin.take()
+    }
+
+    /// Take the read end of the pipe connected to the output of the
+    /// transient service, if [OutputSpec::pipe] is used for
+    /// [RunSystem::stdout].
+    pub fn take_stdout(&mut self) -> Option<OutputPipe> {
+        self.stdout.take()
+    }
+
+    /// Take the read end of the pipe connected to the error output of the
+    /// transient service, if [OutputSpec::pipe] is used for
+    /// [RunSystem::stderr].
+    pub fn take_stderr(&mut self) -> Option<OutputPipe> {
+        self.stderr.take()
+    }
+
+    /// Take the controller side of the pseudo-terminal, if the transient
+    /// service is started with [RunSystem::pty].
+    pub fn take_pty(&mut self) -> Option<Pty> {
+        self.pty.take()
+    }
+
+    /// Get the invocation ID of the transient service, which identifies
+    /// this run of it in the journal.  It's [None] if the transient
+    /// service has not been really started yet.
+    ///
+    /// Read `$INVOCATION_ID` in [systemd.exec(5)](man:systemd.exec(5)) for
+    /// details.
+    ///
+    /// This method is unavailable with the feature `systemd_232`
+    /// disabled.
+    #[cfg(feature = "systemd_232")]
+    pub async fn invocation_id(&self) -> Result<Option<Uuid>> {
+        let iface = zbus_names::InterfaceName::try_from("org.freedesktop.systemd1.Unit")
+            .expect("should not fail with hardcoded str");
+        let v = self
+            .proxy
+            .get(iface.as_ref(), "InvocationID")
+            .await
+            .map_err(Error::QueryPropertyFail)?;
+        Ok(Vec::<u8>::try_from(v)
+            .ok()
+            .and_then(|x| Uuid::from_slice(&x).ok())
+            .filter(|x| !x.is_nil()))
+    }
+
+    /// Stop the transient service with [Self::stop] in the background if
+    /// the [StartedRun] is dropped before it's finished, for example
+    /// because the task waiting for it is cancelled or panics.
+    ///
+    /// Without this, the transient service keeps running until it
+    /// finishes itself or the D-Bus connection is closed.
+    pub fn stop_on_drop(mut self) -> Self {
+        self.on_drop = OnDrop::Stop;
+        self
+    }
+
+    /// Like [Self::stop_on_drop], but kill all the processes of the
+    /// transient service with `SIGKILL` instead of stopping it.
+    pub fn kill_on_drop(mut self) -> Self {
+        self.on_drop = OnDrop::Kill;
+        self
+    }
+
+    fn is_finished(&self) -> bool {
+        matches!(
+            (self.has_job, &self.active_state),
+            (false, Some(ActiveState::Inactive)) | (false, Some(ActiveState::Failed))
+        )
+    }
+
+    /// Get a [Stream](futures::stream::Stream) of the events happening to
+    /// the transient service, see [UnitEvent] for details.  The stream
+    /// ends once the transient service is finished.
+    ///
+    /// The events consumed from the stream are not seen by [Self::wait]
+    /// any more, but it will still work correctly.
+    pub fn events(
+        &mut self,
+    ) -> impl futures::stream::Stream<Item = Result<UnitEvent>> + use<'_, 'a> {
+        futures::stream::unfold(self, |run| async move {
+            run.next_event().await.map(|ev| (ev, run))
+        })
+    }
+
+    /// Sample the current resource usage of the transient service.  See
+    /// [ResourceSample] for details, and [RunSystem] for the accounting
+    /// switched on automatically.
+    ///
+    /// The control group of the transient service is removed by the
+    /// service manager as soon as it's stopped, so some statistics are
+    /// only available in [FinishedRun] if they are recorded by a sample
+    /// taken while it's running, for example with [Self::monitor].  If
+    /// the unified control group is used, the files in the control group
+    /// are read for [FinishedRun::cpu_user_time_usage],
+    /// [FinishedRun::memory_peak] and [FinishedRun::tasks_peak].  They
+    /// are read from `/sys` of the local host, so they are not read if
+    /// the [Manager] may be on another host or in a container (see
+    /// [Manager::address]).
+    pub async fn sample(&self) -> Result<ResourceSample> {
+        let iface = zbus_names::InterfaceName::try_from("org.freedesktop.systemd1.Service")
+            .expect("should not fail with hardcoded str");
+        let names = sample::properties();
+        let values =
+            futures::future::try_join_all(names.iter().map(|k| self.proxy.get(iface.as_ref(), k)))
+                .await
+                .map_err(Error::QueryPropertyFail)?;
+        let props = names.into_iter().zip(values).collect();
+        let sample = sample::parse(&props);
+
+        let mut stat = cgroup::Stat::default();
+        if cfg!(feature = "unified_cgroup") && self.local {
+            let v = self
+                .proxy
+                .get(iface.as_ref(), "ControlGroup")
+                .await
+                .map_err(Error::QueryPropertyFail)?;
+            if let Ok(Value::Str(x)) = v.downcast_ref() {
+                if !x.is_empty() {
+                    stat = cgroup::read(x.as_str());
+                }
+            }
+        }
+        stat.tasks_peak = stat.tasks_peak.max(sample.tasks_current());
+        stat.io_read_bytes = sample.io_read_bytes();
+        stat.io_write_bytes = sample.io_write_bytes();
+        self.sampled
+            .lock()
+            .expect("should not be poisoned")
+            .merge(stat);
+        Ok(sample)
+    }
+
+    /// Get a [Stream](futures::stream::Stream) sampling the resource
+    /// usage of the transient service with [Self::sample] every `period`.
+    /// The stream ends once the transient service is finished.
+    pub fn monitor(
+        &self,
+        period: Duration,
+    ) -> impl futures::stream::Stream<Item = Result<ResourceSample>> + use<'_, 'a> {
+        use futures::stream::StreamExt;
+        let timer = async_io::Timer::interval(period);
+        futures::stream::unfold((self, timer), |(run, mut timer)| async move {
+            timer.next().await;
+            match run.is_running().await {
+                Ok(false) => None,
+                Ok(true) => Some((run.sample().await, (run, timer))),
+                Err(e) => Some((Err(e), (run, timer))),
+            }
+        })
+    }
+
+    // Query the unit directly, as the PropertiesChanged signals may be
+    // consumed by someone else.
+    async fn is_running(&self) -> Result<bool> {
+        let iface = zbus_names::InterfaceName::try_from("org.freedesktop.systemd1.Unit")
+            .expect("should not fail with hardcoded str");
+        let mut changed = std::collections::HashMap::new();
+        for k in ["ActiveState", "Job"] {
+            let v = self
+                .proxy
+                .get(iface.as_ref(), k)
+                .await
+                .map_err(Error::QueryPropertyFail)?;
+            changed.insert(k, Value::from(v));
+        }
         let mut has_job = false;
         let mut active_state = None;
-        let no_job = Value::from((0u32, ObjectPath::try_from("/").unwrap()));
+        for ev in event::parse(&changed) {
+            match ev {
+                UnitEvent::JobQueued(_) => has_job = true,
+                UnitEvent::ActiveState(x) => active_state = Some(x),
+                _ => {}
+            }
+        }
+        Ok(!matches!(
+            (has_job, active_state),
+            (false, Some(ActiveState::Inactive)) | (false, Some(ActiveState::Failed))
+        ))
+    }
+
+    // Get the next event from the PropertiesChanged signals, until the
+    // unit is inactive or failed with no job pending.  The state is kept
+    // in self so this can be resumed after being cancelled.
+    async fn next_event(&mut self) -> Option<Result<UnitEvent>> {
         use futures::stream::StreamExt;
-        while let Some(ev) = stream.next().await {
-            let changed = &ev
-                .args()
-                .map_err(Error::ParsePropertyChangeFail)?
-                .changed_properties;
-            if let Some(Value::Str(state)) = changed.get("ActiveState") {
-                active_state = Some(state.as_str().to_owned());
+        loop {
+            if let Some(ev) = self.pending_events.pop_front() {
+                return Some(Ok(ev));
             }
-            if let Some(job) = changed.get("Job") {
-                has_job = job != &no_job;
+            if self.is_finished() {
+                return None;
             }
-            match (has_job, active_state.as_deref()) {
-                (false, Some("inactive")) => break,
-                (false, Some("failed")) => break,
-                _ => {}
+            let ev = self.stream.next().await?;
+            let changed = match ev.args() {
+                Ok(args) => args.changed_properties,
+                Err(e) => return Some(Err(Error::ParsePropertyChangeFail(e))),
+            };
+            for ev in event::parse(&changed) {
+                match &ev {
+                    UnitEvent::JobQueued(_) => self.has_job = true,
+                    UnitEvent::JobRemoved => self.has_job = false,
+                    UnitEvent::ActiveState(x) => self.active_state = Some(x.clone()),
+                    _ => {}
+                }
+                self.pending_events.push_back(ev);
             }
         }
+    }
+
+    async fn wait_inactive(&mut self) -> Result<()> {
+        while let Some(ev) = self.next_event().await {
+            ev?;
+        }
+        Ok(())
+    }
 
+    async fn finish(&mut self) -> Result<FinishedRun> {
         let iface = zbus_names::InterfaceName::try_from("org.freedesktop.systemd1.Unit")
             .expect("should not fail with hardcoded str");
 
@@ -1277,13 +3038,135 @@ impl StartedRun<'_> {
             }
         };
 
-        let failed = active_state.unwrap() == "failed";
-        let wall_time_usage = Duration::from_micros(time_usage_us);
+        let service_iface = zbus_names::InterfaceName::try_from("org.freedesktop.systemd1.Service")
+            .expect("should not fail with hardcoded str");
+
+        let stat = std::mem::take(&mut *self.sampled.lock().expect("should not be poisoned"));
+
+        let cpu_time_usage = if cfg!(feature = "systemd_227") {
+            self.get_u64(&service_iface, "CPUUsageNSec")
+                .await?
+                .map(Duration::from_nanos)
+        } else {
+            None
+        };
+
+        let (memory_peak, memory_swap_peak) = if cfg!(feature = "systemd_255") {
+            let peak = self.get_u64(&service_iface, "MemoryPeak").await?;
+            let swap_peak = self.get_u64(&service_iface, "MemorySwapPeak").await?;
+            (peak.map(Byte::from), swap_peak.map(Byte::from))
+        } else {
+            (None, None)
+        };
+        let memory_peak = memory_peak.or(stat.memory_peak);
+        let memory_swap_peak = memory_swap_peak.or(stat.memory_swap_peak);
+
+        let io_accounting = cfg!(all(feature = "unified_cgroup", feature = "systemd_231"));
+        let (io_read_bytes, io_write_bytes) = if io_accounting {
+            let read = self.get_u64(&service_iface, "IOReadBytes").await?;
+            let write = self.get_u64(&service_iface, "IOWriteBytes").await?;
+            (read.map(Byte::from), write.map(Byte::from))
+        } else {
+            (None, None)
+        };
+        let io_read_bytes = io_read_bytes.or(stat.io_read_bytes);
+        let io_write_bytes = io_write_bytes.or(stat.io_write_bytes);
+
+        let result = self
+            .proxy
+            .get(service_iface.as_ref(), "Result")
+            .await
+            .map_err(Error::QueryPropertyFail)?;
+        let result = match result.downcast_ref() {
+            Ok(Value::Str(x)) => status::parse_result(x.as_str()),
+            _ => ServiceResult::Other(String::new()),
+        };
+
+        let mut exec_main = [0; 2];
+        for (i, k) in ["ExecMainCode", "ExecMainStatus"].into_iter().enumerate() {
+            let v = self
+                .proxy
+                .get(service_iface.as_ref(), k)
+                .await
+                .map_err(Error::QueryPropertyFail)?;
+            if let Ok(Value::I32(v)) = v.downcast_ref() {
+                exec_main[i] = v;
+            }
+        }
+        let exit_status = status::parse_exit_status(exec_main[0], exec_main[1]);
+
+        // Only needed for reading the journal, so don't fail the run.
+        #[cfg(feature = "systemd_232")]
+        let invocation_id = self.invocation_id().await.ok().flatten();
+        #[cfg(not(feature = "systemd_232"))]
+        let invocation_id = None;
+
+        let failed = self.active_state == Some(ActiveState::Failed);
         Ok(FinishedRun {
             failed,
-            wall_time_usage,
+            aborted: false,
+            result,
+            exit_status,
+            wall_time_usage: Duration::from_micros(time_usage_us),
+            cpu_time_usage,
+            cpu_user_time_usage: stat.cpu_user,
+            cpu_system_time_usage: stat.cpu_system,
+            memory_peak,
+            memory_swap_peak,
+            oom_kills: stat.oom_kills,
+            tasks_peak: stat.tasks_peak,
+            io_read_bytes,
+            io_write_bytes,
+            invocation_id,
+            user_unit: self.user.then(|| self.unit_name.clone()),
+            limits: std::mem::take(&mut self.limits),
         })
     }
+
+    // Query an accounting property of the unit.  Systemd uses u64::MAX
+    // for "no data available", so it's mapped to None.
+    async fn get_u64(
+        &self,
+        iface: &zbus_names::InterfaceName<'_>,
+        name: &str,
+    ) -> Result<Option<u64>> {
+        let v = self
+            .proxy
+            .get(iface.as_ref(), name)
+            .await
+            .map_err(Error::QueryPropertyFail)?;
+        Ok(match v.downcast_ref() {
+            Ok(Value::U64(x)) if x != u64::MAX => Some(x),
+            _ => None,
+        })
+    }
+}
+
+impl Drop for StartedRun<'_> {
+    fn drop(&mut self) {
+        if self.is_finished() {
+            return;
+        }
+        let manager = self.manager.clone();
+        let name = std::mem::take(&mut self.unit_name);
+        let conn = manager.inner().connection().clone();
+        let task = match self.on_drop {
+            OnDrop::Nothing => return,
+            OnDrop::Stop => conn.executor().spawn(
+                async move {
+                    let _ = manager.stop_unit(&name, "replace").await;
+                },
+                "stop transient service",
+            ),
+            OnDrop::Kill => conn.executor().spawn(
+                async move {
+                    let _ = manager.kill_unit(&name, "all", libc::SIGKILL).await;
+                },
+                "kill transient service",
+            ),
+        };
+        task.detach();
+    }
 }
 
 impl FinishedRun {
@@ -1295,8 +3178,185 @@ impl FinishedRun {
         self.failed
     }
 
+    /// Check if the finished transient service has been stopped by
+    /// [StartedRun::wait_timeout] because the deadline is reached.
+    pub fn is_aborted(&self) -> bool {
+        self.aborted
+    }
+
+    /// Get the result of the finished transient service, telling why it
+    /// has finished.  See [ServiceResult] for details.
+    pub fn service_result(&self) -> &ServiceResult {
+        &self.result
+    }
+
+    /// Get how the main process of the finished transient service has
+    /// exited.  It's [None] if the main process has never been started.
+    pub fn exit_status(&self) -> Option<ExitStatus> {
+        self.exit_status
+    }
+
+    /// Check if the main process was killed by `SIGSYS` for calling a
+    /// system call forbidden by the [SystemCallFilter].  It's always
+    /// false if the filter is not set, or the forbidden system calls fail
+    /// with [SystemCallFilter::error_number] instead.
+    ///
+    /// Only the main process is checked: if a child process is killed,
+    /// the main process sees it as an ordinary child exit.
+    pub fn is_killed_by_system_call_filter(&self) -> bool {
+        let signal = self.exit_status.and_then(|x| x.signal());
+        self.limits.system_call_filter_kills && signal == Some(libc::SIGSYS)
+    }
+
+    /// Classify the [Verdict] of the finished transient service with the
+    /// limits configured.  See [Verdict] for details.
+    pub fn verdict(&self) -> Verdict {
+        Verdict::from(self)
+    }
+
     /// Get the usage of wall-clock time of the finished transient service.
     pub fn wall_time_usage(&self) -> Duration {
         self.wall_time_usage
     }
+
+    /// Get the usage of CPU time of the finished transient service,
+    /// including both the user and system time of all the processes in
+    /// the unit.
+    ///
+    /// `CPUAccounting=` is switched on automatically for the transient
+    /// service.  If the CPU time usage is still unavailable (for example,
+    /// the `cpu` controller is not enabled, or the feature `systemd_227`
+    /// is disabled), [Error::UsageUnavailable] will be returned.
+    ///
+    /// Read `CPUAccounting=` in
+    /// [systemd.resource-control(5)](man:systemd.resource-control(5))
+    /// for details.
+    pub fn cpu_time_usage(&self) -> Result<Duration> {
+        self.cpu_time_usage
+            .ok_or(Error::UsageUnavailable("CPU time"))
+    }
+
+    /// Get the usage of CPU time in the user mode of the finished
+    /// transient service.
+    ///
+    /// The service manager only keeps the total CPU time once the
+    /// transient service is stopped, so the value is read from the
+    /// `cpu.stat` file of the control group by the last
+    /// [StartedRun::sample] (including the samples taken by
+    /// [StartedRun::monitor]), and the CPU time used after it is missed.
+    /// If no sample has been taken, or the unified control group is not
+    /// used, [Error::UsageUnavailable] will be returned.
+    pub fn cpu_user_time_usage(&self) -> Result<Duration> {
+        self.cpu_user_time_usage
+            .ok_or(Error::UsageUnavailable("CPU user time"))
+    }
+
+    /// Like [Self::cpu_user_time_usage], but get the usage of CPU time in
+    /// the kernel mode.
+    pub fn cpu_system_time_usage(&self) -> Result<Duration> {
+        self.cpu_system_time_usage
+            .ok_or(Error::UsageUnavailable("CPU system time"))
+    }
+
+    /// Get the peak memory usage of the finished transient service.
+    ///
+    /// `MemoryAccounting=` is switched on automatically for the transient
+    /// service.  The value is queried from the `MemoryPeak` property of
+    /// the unit if the feature `systemd_255` is enabled.  Otherwise it's
+    /// the `memory.peak` file of the control group read by the last
+    /// [StartedRun::sample] (including the samples taken by
+    /// [StartedRun::monitor]), and the usage after it is missed.  If
+    /// neither is available (for example, no sample has been taken, the
+    /// unified control group is not used, or the kernel is older than
+    /// 5.19), [Error::UsageUnavailable] will be returned.
+    ///
+    /// Read `MemoryAccounting=` in
+    /// [systemd.resource-control(5)](man:systemd.resource-control(5))
+    /// for details.
+    pub fn memory_peak(&self) -> Result<Byte> {
+        self.memory_peak.ok_or(Error::UsageUnavailable("memory"))
+    }
+
+    /// Get the peak swap usage of the finished transient service.
+    ///
+    /// Like [Self::memory_peak], but the value is queried from the
+    /// `MemorySwapPeak` property of the unit, or the `memory.swap.peak`
+    /// file read by [StartedRun::sample].  It's always unavailable if the
+    /// unified control group is not used.
+    pub fn memory_swap_peak(&self) -> Result<Byte> {
+        self.memory_swap_peak.ok_or(Error::UsageUnavailable("swap"))
+    }
+
+    /// Get the peak number of tasks of the finished transient service.
+    ///
+    /// The value is the `pids.peak` file of the control group read by
+    /// the last [StartedRun::sample] (including the samples taken by
+    /// [StartedRun::monitor]), and the tasks created after it are missed.
+    /// If the file is unavailable (for example, the unified control group
+    /// is not used, or the kernel is too old to provide it), this is the
+    /// highest `TasksCurrent` seen by the samples instead, and a short
+    /// spike between two samples will be missed as well.  If no sample
+    /// has been taken, [Error::UsageUnavailable] will be returned.
+    pub fn tasks_peak(&self) -> Result<u64> {
+        self.tasks_peak.ok_or(Error::UsageUnavailable("tasks"))
+    }
+
+    /// Get the number of bytes read from block devices by the finished
+    /// transient service.
+    ///
+    /// `IOAccounting=` is switched on automatically for the transient
+    /// service if the features `unified_cgroup` and `systemd_231` are
+    /// enabled.  The value is queried from the `IOReadBytes` property of
+    /// the unit.  The service manager may drop it once the transient
+    /// service is stopped, and then the value seen by the last
+    /// [StartedRun::sample] (including the samples taken by
+    /// [StartedRun::monitor]) is used instead.  If the usage is still
+    /// unavailable (for example, no sample has been taken, or the `io`
+    /// controller is not enabled), [Error::UsageUnavailable] will be
+    /// returned.  The `io` controller is usually not delegated to the
+    /// per-user service manager, so it's likely unavailable with
+    /// [RunUser].
+    ///
+    /// Read `IOAccounting=` in
+    /// [systemd.resource-control(5)](man:systemd.resource-control(5))
+    /// for details.
+    pub fn io_read_bytes(&self) -> Result<Byte> {
+        self.io_read_bytes.ok_or(Error::UsageUnavailable("IO read"))
+    }
+
+    /// Like [Self::io_read_bytes], but get the number of bytes written.
+    pub fn io_write_bytes(&self) -> Result<Byte> {
+        self.io_write_bytes
+            .ok_or(Error::UsageUnavailable("IO write"))
+    }
+
+    /// Get the invocation ID of the finished transient service.  It's
+    /// [None] if the main process has never been started, it can't be
+    /// queried from the service manager, or the feature `systemd_232` is
+    /// disabled.
+    pub fn invocation_id(&self) -> Option<Uuid> {
+        self.invocation_id
+    }
+
+    /// Read the journal entries of the finished transient service, for
+    /// example the lines written to the standard output and error with
+    /// [OutputSpec::journal].  The entries are matched with the
+    /// invocation ID (and the unit name for [RunUser]), so other runs of
+    /// a unit with the same name are not included.
+    ///
+    /// The local journal files are read with `journalctl`, so the user
+    /// needs the permission to read them, for example by being a member
+    /// of the `systemd-journal` group (unless the entries of a [RunUser]
+    /// unit are in the journal file of the user).  The last entries may
+    /// be missing if the journal has not processed them yet.
+    ///
+    /// An empty [Vec] will be returned if the invocation ID is [None].
+    pub async fn journal(&self) -> Result<Vec<JournalEntry>> {
+        match self.invocation_id {
+            Some(id) => journal::read(id, self.user_unit.as_deref())
+                .await
+                .map_err(Error::ReadJournalFail),
+            None => Ok(vec![]),
+        }
+    }
 }
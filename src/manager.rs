@@ -0,0 +1,140 @@
+use crate::{sd, Error, Result, RunSystem, RunUser, StartedRun};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use zbus::Connection;
+
+/// A handle of a service manager, owning the D-Bus connection to it.
+///
+/// [RunSystem::start] and [RunUser::start] open a new D-Bus connection
+/// for each transient service.  Use a [Manager] instead to start many
+/// transient services through one connection.
+#[derive(Clone)]
+pub struct Manager {
+    bus: Connection,
+    proxy: sd::SystemdManagerProxy<'static>,
+    // For generating distinct default unit names on one connection.
+    seq: Arc<AtomicU64>,
+    // If the service manager is known to run on this host, so /sys can be
+    // used to check the settings.
+    local: bool,
+}
+
+impl Manager {
+    /// Connect to the system service manager through the system bus.
+    pub async fn system() -> Result<Self> {
+        let bus = Connection::system()
+            .await
+            .map_err(Error::DBusConnectionFail)?;
+        Ok(Self::from_connection(bus).await?.local())
+    }
+
+    /// Connect to the per-user service manager of the login session
+    /// through the session bus.
+    pub async fn user() -> Result<Self> {
+        let bus = Connection::session()
+            .await
+            .map_err(Error::DBusConnectionFail)?;
+        Ok(Self::from_connection(bus).await?.local())
+    }
+
+    /// Connect to a service manager through the D-Bus message bus at the
+    /// address, for example `unix:path=/run/user/1000/bus`.  Use
+    /// [Self::private] for the private socket of a service manager.
+    ///
+    /// Read the
+    /// [D-Bus specification](https://dbus.freedesktop.org/doc/dbus-specification.html#addresses)
+    /// for the format of the address.
+    ///
+    /// The service manager may be on another host or in a container, so
+    /// the settings depending on this host, like [RunSystem::allowed_cpus],
+    /// are not checked before starting the transient services, and the
+    /// control groups are not read by [StartedRun::sample].
+    pub async fn address<T: AsRef<str>>(address: T) -> Result<Self> {
+        let bus = zbus::connection::Builder::address(address.as_ref())
+            .map_err(Error::DBusConnectionFail)?
+            .build()
+            .await
+            .map_err(Error::DBusConnectionFail)?;
+        Self::from_connection(bus).await
+    }
+
+    /// Connect to a service manager directly through its private socket
+    /// at the address, without a message bus.  For example,
+    /// `unix:path=/run/systemd/private` for the system service manager
+    /// (only accessible by `root`), or
+    /// `unix:path=/run/user/1000/systemd/private` for the per-user service
+    /// manager.
+    ///
+    /// The address format is same as [Self::address], but the connection
+    /// is peer-to-peer.  The settings depending on this host are not
+    /// checked like [Self::address].
+    pub async fn private<T: AsRef<str>>(address: T) -> Result<Self> {
+        let bus = zbus::connection::Builder::address(address.as_ref())
+            .map_err(Error::DBusConnectionFail)?
+            .p2p()
+            .build()
+            .await
+            .map_err(Error::DBusConnectionFail)?;
+        Self::from_connection(bus).await
+    }
+
+    /// Use an established D-Bus connection to a service manager.  The
+    /// settings depending on this host are not checked like
+    /// [Self::address].
+    pub async fn from_connection(bus: Connection) -> Result<Self> {
+        let proxy = sd::SystemdManagerProxy::builder(&bus)
+            .build()
+            .await
+            .expect("should not fail with hardcoded parameters in sd.rs");
+        Ok(Self {
+            bus,
+            proxy,
+            seq: Arc::new(AtomicU64::new(0)),
+            local: false,
+        })
+    }
+
+    fn local(self) -> Self {
+        Self {
+            local: true,
+            ..self
+        }
+    }
+
+    /// Start the transient service described by a [RunSystem] on this
+    /// service manager.
+    ///
+    /// The [Identity](crate::Identity) of the [RunSystem] is still
+    /// honored, so you'll need to be the `root` user for most identities.
+    pub async fn start<'a>(&self, run: RunSystem) -> Result<StartedRun<'a>> {
+        run.validate(self.local)?;
+        run.start_with(self).await
+    }
+
+    /// Start the transient service described by a [RunUser] on this
+    /// service manager.  The manager should be the per-user service
+    /// manager, or the transient service will run as the user of the
+    /// service manager.
+    pub async fn start_user<'a>(&self, run: RunUser) -> Result<StartedRun<'a>> {
+        run.0.validate(self.local)?;
+        run.0.start_with(self).await
+    }
+}
+
+pub fn bus(m: &Manager) -> &Connection {
+    &m.bus
+}
+
+pub fn proxy(m: &Manager) -> &sd::SystemdManagerProxy<'static> {
+    &m.proxy
+}
+
+// If the service manager is connected with Manager::system or
+// Manager::user, and so known to run on this host.
+pub fn is_local(m: &Manager) -> bool {
+    m.local
+}
+
+pub fn next_seq(m: &Manager) -> u64 {
+    m.seq.fetch_add(1, Ordering::Relaxed)
+}
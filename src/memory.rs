@@ -0,0 +1,175 @@
+use byte_unit::Byte;
+use zbus::zvariant::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Priv {
+    Bytes(u64),
+    Infinity,
+    #[allow(dead_code)]
+    Scale(u32),
+}
+
+/// A memory limit of a transient service, in bytes, as a percentage of
+/// the physical memory, or "infinity".
+///
+/// A [Byte] can be converted into a [MemoryLimit].  Any [Byte] exceeding
+/// [u64::MAX] bytes is trimmed to [u64::MAX] bytes silently, which is
+/// same as [MemoryLimit::infinity].
+///
+/// Read `MemoryMax=` in
+/// [systemd.resource-control(5)](man:systemd.resource-control(5)) for
+/// details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryLimit(Priv);
+
+impl MemoryLimit {
+    /// No limit.
+    pub fn infinity() -> Self {
+        Self(Priv::Infinity)
+    }
+
+    /// The percentage of the physical memory on the system.  If `percent`
+    /// is greater than 100, [None] will be returned.
+    ///
+    /// This setting will be unavailable if the feature `systemd_247` is
+    /// disabled.
+    #[cfg(feature = "systemd_247")]
+    pub fn percent(percent: u8) -> Option<Self> {
+        match percent {
+            0..=100 => Some(Self(Priv::Scale(scale_from_percent(percent)))),
+            _ => None,
+        }
+    }
+}
+
+impl From<Byte> for MemoryLimit {
+    fn from(x: Byte) -> Self {
+        Self(Priv::Bytes(x.as_u64()))
+    }
+}
+
+// UINT32_SCALE_FROM_PERCENT in systemd/src/basic/percent-util.h.
+pub fn scale_from_percent(percent: u8) -> u32 {
+    let x = (u64::from(percent) * u64::from(u32::MAX) + 50) / 100;
+    x.try_into().unwrap_or(u32::MAX)
+}
+
+// The D-Bus property and value of a MemoryLimit.  The percentage is set
+// with the *Scale variant of the property.
+pub fn marshal(
+    name: (&'static str, &'static str),
+    x: MemoryLimit,
+) -> (&'static str, Value<'static>) {
+    match x.0 {
+        Priv::Bytes(x) => (name.0, Value::from(x)),
+        Priv::Infinity => (name.0, Value::from(u64::MAX)),
+        Priv::Scale(x) => (name.1, Value::from(x)),
+    }
+}
+
+/// The action taken when the OOM killer is invoked for a transient
+/// service.
+///
+/// Read `OOMPolicy=` in [systemd.service(5)](man:systemd.service(5)) for
+/// details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OomPolicy {
+    /// Log the event and continue running.
+    Continue,
+    /// Stop the transient service cleanly.
+    Stop,
+    /// Kill all the remaining processes of the transient service.
+    Kill,
+}
+
+impl OomPolicy {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Continue => "continue",
+            Self::Stop => "stop",
+            Self::Kill => "kill",
+        }
+    }
+}
+
+/// The action of `systemd-oomd` on a transient service.
+///
+/// Read `ManagedOOMSwap=` in
+/// [systemd.resource-control(5)](man:systemd.resource-control(5)) for
+/// details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManagedOom {
+    /// Not monitored.
+    Auto,
+    /// Killed if the pressure or the swap usage exceeds the limit.
+    Kill,
+}
+
+impl ManagedOom {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Kill => "kill",
+        }
+    }
+}
+
+/// The preference of `systemd-oomd` to kill a transient service.
+///
+/// Read `ManagedOOMPreference=` in
+/// [systemd.resource-control(5)](man:systemd.resource-control(5)) for
+/// details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManagedOomPreference {
+    /// Default.
+    None,
+    /// Killed only if there is no other candidate.
+    Avoid,
+    /// Never killed.
+    Omit,
+}
+
+impl ManagedOomPreference {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Avoid => "avoid",
+            Self::Omit => "omit",
+        }
+    }
+}
+
+/// The OOM related settings of a transient service.
+#[derive(Default)]
+pub struct Oom {
+    pub policy: Option<OomPolicy>,
+    pub score_adjust: Option<i32>,
+    pub managed_swap: Option<ManagedOom>,
+    pub managed_memory_pressure: Option<ManagedOom>,
+    pub managed_memory_pressure_limit: Option<u8>,
+    pub managed_preference: Option<ManagedOomPreference>,
+}
+
+pub fn marshal_oom(oom: Oom) -> Vec<(&'static str, Value<'static>)> {
+    let mut r = vec![];
+    if let Some(x) = oom.policy {
+        r.push(("OOMPolicy", Value::from(x.as_str())));
+    }
+    if let Some(x) = oom.score_adjust {
+        r.push(("OOMScoreAdjust", Value::from(x)));
+    }
+    if let Some(x) = oom.managed_swap {
+        r.push(("ManagedOOMSwap", Value::from(x.as_str())));
+    }
+    if let Some(x) = oom.managed_memory_pressure {
+        r.push(("ManagedOOMMemoryPressure", Value::from(x.as_str())));
+    }
+    if let Some(x) = oom.managed_memory_pressure_limit {
+        let v = Value::from(scale_from_percent(x));
+        r.push(("ManagedOOMMemoryPressureLimit", v));
+    }
+    if let Some(x) = oom.managed_preference {
+        r.push(("ManagedOOMPreference", Value::from(x.as_str())));
+    }
+    r
+}
@@ -0,0 +1,176 @@
+use zbus::zvariant::Value;
+
+/// Mount the OS directories read-only for the executed processes.
+///
+/// Read `ProtectSystem=` in [systemd.exec(5)](man:systemd.exec(5)) for
+/// details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProtectSystem {
+    /// Mount `/usr` and the boot loader directories read-only.
+    True,
+    /// Like [ProtectSystem::True], and also mount `/etc` read-only.
+    Full,
+    /// Mount the entire file system hierarchy read-only, except the API
+    /// file systems `/dev`, `/proc`, and `/sys`.  Use
+    /// [RunSystem::read_write_paths](crate::RunSystem::read_write_paths)
+    /// for the paths needing write access.
+    Strict,
+}
+
+pub fn marshal_system(x: ProtectSystem) -> &'static str {
+    match x {
+        ProtectSystem::True => "yes",
+        ProtectSystem::Full => "full",
+        ProtectSystem::Strict => "strict",
+    }
+}
+
+/// Restrict the access to the home directories for the executed
+/// processes.
+///
+/// Read `ProtectHome=` in [systemd.exec(5)](man:systemd.exec(5)) for
+/// details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProtectHome {
+    /// Make `/home`, `/root`, and `/run/user` inaccessible and empty.
+    True,
+    /// Mount `/home`, `/root`, and `/run/user` read-only.
+    ReadOnly,
+    /// Mount read-only tmpfs on `/home`, `/root`, and `/run/user`.
+    ///
+    /// This variant is unavailable with the feature `systemd_244`
+    /// disabled.
+    #[cfg(feature = "systemd_244")]
+    Tmpfs,
+}
+
+pub fn marshal_home(x: ProtectHome) -> &'static str {
+    match x {
+        ProtectHome::True => "yes",
+        ProtectHome::ReadOnly => "read-only",
+        #[cfg(feature = "systemd_244")]
+        ProtectHome::Tmpfs => "tmpfs",
+    }
+}
+
+/// A path in the path lists like
+/// [RunSystem::read_only_paths](crate::RunSystem::read_only_paths).  A
+/// [&str](str) or [String] can be converted into an [AccessPath].
+///
+/// Read `ReadOnlyPaths=` in [systemd.exec(5)](man:systemd.exec(5)) for
+/// details.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccessPath {
+    path: String,
+    ignore_nonexist: bool,
+    relative_to_root: bool,
+}
+
+impl AccessPath {
+    /// Create an [AccessPath].  The path must be absolute, or
+    /// [Error::InvalidPath](crate::Error::InvalidPath) will be returned
+    /// when starting the transient service.  So the prefixes can't be
+    /// written in the path, use [Self::ignore_nonexist] and
+    /// [Self::relative_to_root] instead.
+    pub fn new<T: AsRef<str>>(path: T) -> Self {
+        Self {
+            path: path.as_ref().to_owned(),
+            ignore_nonexist: false,
+            relative_to_root: false,
+        }
+    }
+
+    /// Ignore the path if it does not exist, i. e. the `-` prefix.
+    pub fn ignore_nonexist(self) -> Self {
+        Self {
+            ignore_nonexist: true,
+            ..self
+        }
+    }
+
+    /// Resolve the path relative to the root directory of the unit
+    /// instead of the host, i. e. the `+` prefix.
+    pub fn relative_to_root(self) -> Self {
+        Self {
+            relative_to_root: true,
+            ..self
+        }
+    }
+}
+
+impl From<&str> for AccessPath {
+    fn from(x: &str) -> Self {
+        Self::new(x)
+    }
+}
+
+impl From<String> for AccessPath {
+    fn from(path: String) -> Self {
+        Self {
+            path,
+            ignore_nonexist: false,
+            relative_to_root: false,
+        }
+    }
+}
+
+// Systemd accepts the `-` prefix before the `+` prefix.  The path itself
+// starts with `/` (see PathLists::check), so it can't be mistaken for a
+// prefix.  Unlike the mounts, the list is an array of strings on D-Bus
+// and systemd reads each path verbatim (quoting it itself when writing
+// the unit file), so spaces and backslashes must not be escaped.
+fn marshal_path(p: AccessPath) -> String {
+    let mut s = String::new();
+    if p.ignore_nonexist {
+        s.push('-');
+    }
+    if p.relative_to_root {
+        s.push('+');
+    }
+    s + &p.path
+}
+
+/// The path lists of a transient service.
+#[derive(Default)]
+pub struct PathLists {
+    pub read_only: Vec<AccessPath>,
+    pub read_write: Vec<AccessPath>,
+    pub inaccessible: Vec<AccessPath>,
+    pub exec: Vec<AccessPath>,
+    pub no_exec: Vec<AccessPath>,
+}
+
+impl PathLists {
+    // Return the first path which is not absolute, including a path
+    // starting with a `-` or `+` prefix written by hand.
+    pub fn check(&self) -> Option<&str> {
+        [
+            &self.read_only,
+            &self.read_write,
+            &self.inaccessible,
+            &self.exec,
+            &self.no_exec,
+        ]
+        .into_iter()
+        .flatten()
+        .find(|x| !x.path.starts_with('/'))
+        .map(|x| x.path.as_str())
+    }
+
+    pub fn marshal(self) -> Vec<(&'static str, Value<'static>)> {
+        let mut r = vec![];
+        for (k, v) in [
+            ("ReadOnlyPaths", self.read_only),
+            ("ReadWritePaths", self.read_write),
+            ("InaccessiblePaths", self.inaccessible),
+            ("ExecPaths", self.exec),
+            ("NoExecPaths", self.no_exec),
+        ] {
+            if !v.is_empty() {
+                let v = v.into_iter().map(marshal_path).collect::<Vec<_>>();
+                r.push((k, Value::from(v)));
+            }
+        }
+        r
+    }
+}
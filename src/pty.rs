@@ -0,0 +1,120 @@
+use async_io::Async;
+use futures::io::{AsyncRead, AsyncWrite};
+use std::ffi::CStr;
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+use std::os::unix::fs::OpenOptionsExt;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// The controller side of a pseudo-terminal connected to a transient
+/// service, created with [RunSystem::pty](crate::RunSystem::pty) and
+/// taken with [StartedRun::take_pty](crate::StartedRun::take_pty).
+///
+/// Reading from it gets the output of the transient service, and writing
+/// to it sends the input.  Reading returns EOF once all the processes of
+/// the transient service have closed the terminal.
+pub struct Pty(Async<File>);
+
+impl Pty {
+    /// Set the window size of the terminal, in rows and columns.  As the
+    /// terminal is the controlling terminal of the main process of the
+    /// transient service, its foreground process group receives
+    /// `SIGWINCH`.
+    pub fn set_window_size(&self, rows: u16, cols: u16) -> io::Result<()> {
+        let ws = libc::winsize {
+            ws_row: rows,
+            ws_col: cols,
+            ws_xpixel: 0,
+            ws_ypixel: 0,
+        };
+        // SAFETY: TIOCSWINSZ only reads the winsize structure.
+        let r = unsafe { libc::ioctl(self.0.as_raw_fd(), libc::TIOCSWINSZ, &ws) };
+        match r {
+            -1 => Err(io::Error::last_os_error()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Get the window size of the terminal, in rows and columns.
+    pub fn window_size(&self) -> io::Result<(u16, u16)> {
+        // SAFETY: winsize is plain old data, and TIOCGWINSZ fills it.
+        let mut ws: libc::winsize = unsafe { std::mem::zeroed() };
+        let r = unsafe { libc::ioctl(self.0.as_raw_fd(), libc::TIOCGWINSZ, &mut ws) };
+        match r {
+            -1 => Err(io::Error::last_os_error()),
+            _ => Ok((ws.ws_row, ws.ws_col)),
+        }
+    }
+}
+
+impl AsyncRead for Pty {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        // Linux reports EIO instead of EOF once the replica side is
+        // closed by everyone.
+        match Pin::new(&mut self.0).poll_read(cx, buf) {
+            Poll::Ready(Err(e)) if e.raw_os_error() == Some(libc::EIO) => Poll::Ready(Ok(0)),
+            x => x,
+        }
+    }
+}
+
+impl AsyncWrite for Pty {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.0).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_close(cx)
+    }
+}
+
+fn check(r: libc::c_int) -> io::Result<libc::c_int> {
+    match r {
+        -1 => Err(io::Error::last_os_error()),
+        _ => Ok(r),
+    }
+}
+
+/// Allocate a pseudo-terminal pair, returning the controller, the
+/// replica, and the path of the replica.
+pub fn open() -> io::Result<(Pty, OwnedFd, String)> {
+    let flags = libc::O_RDWR | libc::O_NOCTTY | libc::O_CLOEXEC;
+    // SAFETY: posix_openpt returns a new file descriptor owned by us.
+    let controller = unsafe { OwnedFd::from_raw_fd(check(libc::posix_openpt(flags))?) };
+    let fd = controller.as_raw_fd();
+
+    // SAFETY: fd is a valid pseudo-terminal controller, and ptsname_r
+    // NUL-terminates the path on success.
+    let path = unsafe {
+        check(libc::grantpt(fd))?;
+        check(libc::unlockpt(fd))?;
+        let mut buf = [0 as libc::c_char; 128];
+        match libc::ptsname_r(fd, buf.as_mut_ptr(), buf.len()) {
+            0 => CStr::from_ptr(buf.as_ptr()).to_string_lossy().into_owned(),
+            e => return Err(io::Error::from_raw_os_error(e)),
+        }
+    };
+
+    let replica = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .custom_flags(libc::O_NOCTTY)
+        .open(&path)?;
+
+    let controller = Pty(Async::new(File::from(controller))?);
+    Ok((controller, OwnedFd::from(replica), path))
+}
@@ -0,0 +1,110 @@
+use byte_unit::Byte;
+
+/// A resource limit of the executed processes, set with
+/// [RunSystem::limit](crate::RunSystem::limit).
+///
+/// Read `LimitCPU=` etc. in [systemd.exec(5)](man:systemd.exec(5)) and
+/// [prlimit(2)](man:prlimit(2)) for details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rlimit {
+    /// `RLIMIT_CPU`, the CPU time in seconds.  The process receives
+    /// `SIGXCPU` at the soft limit and `SIGKILL` at the hard limit.
+    Cpu,
+    /// `RLIMIT_FSIZE`, the size of files the process may create in bytes.
+    Fsize,
+    /// `RLIMIT_DATA`, the size of the data segment in bytes.
+    Data,
+    /// `RLIMIT_STACK`, the size of the process stack in bytes.
+    Stack,
+    /// `RLIMIT_CORE`, the size of core dump files in bytes.
+    Core,
+    /// `RLIMIT_RSS`, the resident set size in bytes.  It has no effect on
+    /// Linux.
+    Rss,
+    /// `RLIMIT_NOFILE`, **one greater than** the maximum file descriptor
+    /// number the process may open.
+    Nofile,
+    /// `RLIMIT_AS`, the size of the virtual address space in bytes.
+    As,
+    /// `RLIMIT_NPROC`, the number of threads for the real user ID of the
+    /// process.
+    Nproc,
+    /// `RLIMIT_MEMLOCK`, the size of memory locked into RAM in bytes.
+    Memlock,
+    /// `RLIMIT_LOCKS`, the number of file locks.  It has no effect on
+    /// Linux.
+    Locks,
+    /// `RLIMIT_SIGPENDING`, the number of signals queued for the real user
+    /// ID of the process.
+    Sigpending,
+    /// `RLIMIT_MSGQUEUE`, the size of POSIX message queues in bytes.
+    Msgqueue,
+    /// `RLIMIT_NICE`, the ceiling of the nice value as `20 - nice`, in
+    /// `[1, 40]`.
+    Nice,
+    /// `RLIMIT_RTPRIO`, the ceiling of the real-time priority.
+    Rtprio,
+    /// `RLIMIT_RTTIME`, the CPU time in microseconds a real-time process
+    /// may consume without making a blocking system call.
+    Rttime,
+}
+
+// The D-Bus properties and values for the hard and soft limit.  Systemd
+// uses RLIM_INFINITY, i. e. u64::MAX for "infinity".
+pub fn marshal(r: Rlimit, soft: RlimitValue, hard: RlimitValue) -> [(&'static str, u64); 2] {
+    let raw = |x| match x {
+        RlimitValue::Value(x) => x,
+        RlimitValue::Infinity => u64::MAX,
+    };
+    let (k_hard, k_soft) = properties(r);
+    [(k_hard, raw(hard)), (k_soft, raw(soft))]
+}
+
+fn properties(r: Rlimit) -> (&'static str, &'static str) {
+    use Rlimit::*;
+    match r {
+        Cpu => ("LimitCPU", "LimitCPUSoft"),
+        Fsize => ("LimitFSIZE", "LimitFSIZESoft"),
+        Data => ("LimitDATA", "LimitDATASoft"),
+        Stack => ("LimitSTACK", "LimitSTACKSoft"),
+        Core => ("LimitCORE", "LimitCORESoft"),
+        Rss => ("LimitRSS", "LimitRSSSoft"),
+        Nofile => ("LimitNOFILE", "LimitNOFILESoft"),
+        As => ("LimitAS", "LimitASSoft"),
+        Nproc => ("LimitNPROC", "LimitNPROCSoft"),
+        Memlock => ("LimitMEMLOCK", "LimitMEMLOCKSoft"),
+        Locks => ("LimitLOCKS", "LimitLOCKSSoft"),
+        Sigpending => ("LimitSIGPENDING", "LimitSIGPENDINGSoft"),
+        Msgqueue => ("LimitMSGQUEUE", "LimitMSGQUEUESoft"),
+        Nice => ("LimitNICE", "LimitNICESoft"),
+        Rtprio => ("LimitRTPRIO", "LimitRTPRIOSoft"),
+        Rttime => ("LimitRTTIME", "LimitRTTIMESoft"),
+    }
+}
+
+/// The value of a resource limit, in the unit documented for each
+/// [Rlimit].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RlimitValue {
+    /// A finite limit.
+    Value(u64),
+    /// No limit, `RLIM_INFINITY`.
+    Infinity,
+}
+
+impl From<u64> for RlimitValue {
+    fn from(x: u64) -> Self {
+        Self::Value(x)
+    }
+}
+
+impl From<Byte> for RlimitValue {
+    /// Any value exceeding [u64::MAX] bytes is trimmed to [u64::MAX]
+    /// bytes, i. e. [RlimitValue::Infinity].
+    fn from(x: Byte) -> Self {
+        match x.as_u64() {
+            u64::MAX => Self::Infinity,
+            x => Self::Value(x),
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use byte_unit::Byte;
+use std::collections::HashMap;
+use std::time::Duration;
+use zbus::zvariant::{OwnedValue, Value};
+
+/// The resource usage of a running transient service, produced by
+/// [StartedRun::sample](crate::StartedRun::sample).
+///
+/// Each value is [None] if it's not available from the service manager,
+/// for example if the corresponding controller is not enabled.  Read
+/// [systemd.resource-control(5)](man:systemd.resource-control(5)) for
+/// details.
+#[derive(Debug, Clone, Default)]
+pub struct ResourceSample {
+    memory_current: Option<Byte>,
+    cpu_time_usage: Option<Duration>,
+    tasks_current: Option<u64>,
+    io_read_bytes: Option<Byte>,
+    io_write_bytes: Option<Byte>,
+}
+
+impl ResourceSample {
+    /// Get the current memory usage, from `MemoryCurrent`.
+    pub fn memory_current(&self) -> Option<Byte> {
+        self.memory_current
+    }
+
+    /// Get the CPU time consumed so far, from `CPUUsageNSec`.
+    pub fn cpu_time_usage(&self) -> Option<Duration> {
+        self.cpu_time_usage
+    }
+
+    /// Get the current number of tasks, from `TasksCurrent`.
+    pub fn tasks_current(&self) -> Option<u64> {
+        self.tasks_current
+    }
+
+    /// Get the number of bytes read so far, from `IOReadBytes`.
+    pub fn io_read_bytes(&self) -> Option<Byte> {
+        self.io_read_bytes
+    }
+
+    /// Get the number of bytes written so far, from `IOWriteBytes`.
+    pub fn io_write_bytes(&self) -> Option<Byte> {
+        self.io_write_bytes
+    }
+}
+
+// Get the properties of the unit needed for a ResourceSample.  Those
+// not supported by the service manager are skipped, as querying them
+// would fail.
+pub fn properties() -> Vec<&'static str> {
+    let io = cfg!(all(feature = "unified_cgroup", feature = "systemd_231"));
+    [
+        ("MemoryCurrent", cfg!(feature = "systemd_227")),
+        ("CPUUsageNSec", cfg!(feature = "systemd_227")),
+        ("TasksCurrent", cfg!(feature = "systemd_227")),
+        ("IOReadBytes", io),
+        ("IOWriteBytes", io),
+    ]
+    .into_iter()
+    .filter_map(|(k, v)| v.then_some(k))
+    .collect()
+}
+
+pub fn parse(props: &HashMap<&str, OwnedValue>) -> ResourceSample {
+    // Systemd uses u64::MAX for "no data available".
+    let get = |k: &str| match props.get(k).map(|v| v.downcast_ref()) {
+        Some(Ok(Value::U64(x))) if x != u64::MAX => Some(x),
+        _ => None,
+    };
+    ResourceSample {
+        memory_current: get("MemoryCurrent").map(Byte::from),
+        cpu_time_usage: get("CPUUsageNSec").map(Duration::from_nanos),
+        tasks_current: get("TasksCurrent"),
+        io_read_bytes: get("IOReadBytes").map(Byte::from),
+        io_write_bytes: get("IOWriteBytes").map(Byte::from),
+    }
+}
@@ -27,4 +27,6 @@ pub trait SystemdManager {
 
     #[zbus(object = "SystemdJob")]
     fn stop_unit(&self, name: &str, mode: &str);
+
+    fn kill_unit(&self, name: &str, who: &str, signal: i32) -> zbus::Result<()>;
 }
@@ -0,0 +1,153 @@
+use zbus::zvariant::Value;
+
+/// The system call filter of a transient service, applied with seccomp.
+///
+/// An allow-list created with [SystemCallFilter::allow] permits the
+/// listed system calls only, and a deny-list created with
+/// [SystemCallFilter::deny] forbids the listed system calls.  By default
+/// a process calling a forbidden system call is killed by `SIGSYS`, see
+/// [FinishedRun::is_killed_by_system_call_filter](crate::FinishedRun::is_killed_by_system_call_filter).
+///
+/// Read `SystemCallFilter=` in [systemd.exec(5)](man:systemd.exec(5))
+/// for details, and the list of the groups.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SystemCallFilter {
+    allow: bool,
+    syscalls: Vec<String>,
+    except: Vec<String>,
+    error_number: Option<i32>,
+    architectures: Vec<String>,
+    log: Vec<String>,
+}
+
+impl SystemCallFilter {
+    fn new(allow: bool) -> Self {
+        Self {
+            allow,
+            syscalls: vec![],
+            except: vec![],
+            error_number: None,
+            architectures: vec![],
+            log: vec![],
+        }
+    }
+
+    /// Create an empty allow-list.  Note that an allow-list without any
+    /// system call is ignored, instead of forbidding everything.
+    pub fn allow() -> Self {
+        Self::new(true)
+    }
+
+    /// Create an empty deny-list.
+    pub fn deny() -> Self {
+        Self::new(false)
+    }
+
+    /// Add a system call, for example `"execve"`, or a group of system
+    /// calls with the leading `@`, for example `"@mount"`, into the list.
+    pub fn syscall<T: AsRef<str>>(mut self, name: T) -> Self {
+        self.syscalls.push(name.as_ref().to_owned());
+        self
+    }
+
+    /// Add a group of system calls, for example `"system-service"` for
+    /// `@system-service`, into the list.
+    pub fn group<T: AsRef<str>>(self, name: T) -> Self {
+        self.syscall(format!("@{}", name.as_ref()))
+    }
+
+    /// Exclude a system call or a group from the list, like
+    /// [Self::syscall].  For example, an allow-list of `@system-service`
+    /// except `@privileged`.
+    pub fn except<T: AsRef<str>>(mut self, name: T) -> Self {
+        self.except.push(name.as_ref().to_owned());
+        self
+    }
+
+    /// Make the forbidden system calls fail with the error number, for
+    /// example [libc::EPERM], instead of killing the process.
+    ///
+    /// Read `SystemCallErrorNumber=` in
+    /// [systemd.exec(5)](man:systemd.exec(5)) for details.
+    pub fn error_number(self, errno: i32) -> Self {
+        Self {
+            error_number: Some(errno),
+            ..self
+        }
+    }
+
+    /// Allow the system calls of the architecture, for example `"native"`
+    /// or `"x86-64"`.  If any architecture is added, the system calls of
+    /// the other architectures are forbidden.
+    ///
+    /// Read `SystemCallArchitectures=` in
+    /// [systemd.exec(5)](man:systemd.exec(5)) for details.
+    pub fn architecture<T: AsRef<str>>(mut self, arch: T) -> Self {
+        self.architectures.push(arch.as_ref().to_owned());
+        self
+    }
+
+    /// Log the invocations of a system call or a group, like
+    /// [Self::syscall], to the audit log.  It's independent of the
+    /// filter.
+    ///
+    /// Read `SystemCallLog=` in [systemd.exec(5)](man:systemd.exec(5))
+    /// for details.
+    ///
+    /// This setting is unavailable with the feature `systemd_247`
+    /// disabled.
+    #[cfg(feature = "systemd_247")]
+    pub fn log<T: AsRef<str>>(mut self, name: T) -> Self {
+        self.log.push(name.as_ref().to_owned());
+        self
+    }
+}
+
+// Read syscall_filter_sets in systemd/src/shared/seccomp-util.c for the
+// group names.
+fn is_valid_name(s: &str) -> bool {
+    let (s, group) = match s.strip_prefix('@') {
+        Some(s) => (s, true),
+        None => (s, false),
+    };
+    let ok = |c| matches!(c, b'a'..=b'z' | b'0'..=b'9' | b'_') || (group && c == b'-');
+    !s.is_empty() && s.bytes().all(ok)
+}
+
+// Return the first invalid system call or group name.
+pub fn check(f: &SystemCallFilter) -> Option<&str> {
+    f.syscalls
+        .iter()
+        .chain(&f.except)
+        .chain(&f.log)
+        .find(|x| !is_valid_name(x))
+        .map(String::as_str)
+}
+
+// If a process calling a forbidden system call is killed.
+pub fn kills(f: &SystemCallFilter) -> bool {
+    f.error_number.is_none() && !f.syscalls.is_empty()
+}
+
+// Each SystemCallFilter property is merged into the previous ones by
+// systemd: the names are added if the bool is same as the first one, or
+// removed otherwise.
+pub fn marshal(f: SystemCallFilter) -> Vec<(&'static str, Value<'static>)> {
+    let mut r = vec![];
+    if !f.syscalls.is_empty() {
+        r.push(("SystemCallFilter", Value::from((f.allow, f.syscalls))));
+        if !f.except.is_empty() {
+            r.push(("SystemCallFilter", Value::from((!f.allow, f.except))));
+        }
+    }
+    if let Some(x) = f.error_number {
+        r.push(("SystemCallErrorNumber", Value::from(x)));
+    }
+    if !f.architectures.is_empty() {
+        r.push(("SystemCallArchitectures", Value::from(f.architectures)));
+    }
+    if !f.log.is_empty() {
+        r.push(("SystemCallLog", Value::from((true, f.log))));
+    }
+    r
+}
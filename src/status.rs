@@ -0,0 +1,106 @@
+/// The result of a finished transient service, telling why it has
+/// finished.
+///
+/// Read `Result` in
+/// [org.freedesktop.systemd1(5)](man:org.freedesktop.systemd1(5)) and
+/// `$SERVICE_RESULT` in [systemd.exec(5)](man:systemd.exec(5)) for
+/// details.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServiceResult {
+    /// The service finished successfully.
+    Success,
+    /// Not enough resources were available to run the service.
+    Resources,
+    /// The service manager was unhappy about the behavior of the service.
+    Protocol,
+    /// A timeout occurred, for example `RuntimeMaxSec=` is exceeded.
+    Timeout,
+    /// The main process exited with a non-zero exit code.
+    ExitCode,
+    /// The main process was terminated by an uncaught signal.
+    Signal,
+    /// The main process dumped core.
+    CoreDump,
+    /// The watchdog keep-alive ping was missed.
+    Watchdog,
+    /// The start rate limit of the unit was hit.
+    StartLimitHit,
+    /// A process of the service was killed by the out-of-memory killer.
+    OomKill,
+    /// The condition for starting the service failed.
+    ExecCondition,
+    /// A result not known by this crate.
+    Other(String),
+}
+
+/// How the main process of a transient service has exited.
+///
+/// Read `ExecMainCode` and `ExecMainStatus` in
+/// [org.freedesktop.systemd1(5)](man:org.freedesktop.systemd1(5)) and
+/// [waitid(2)](man:waitid(2)) for details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitStatus {
+    /// The main process exited with the exit code.
+    Exited(i32),
+    /// The main process was killed by the signal.
+    Signaled {
+        /// The number of the signal.
+        signal: i32,
+        /// If the main process dumped core.
+        core_dumped: bool,
+    },
+}
+
+impl ExitStatus {
+    /// Get the exit code if the main process has exited normally.
+    pub fn code(&self) -> Option<i32> {
+        match self {
+            Self::Exited(x) => Some(*x),
+            Self::Signaled { .. } => None,
+        }
+    }
+
+    /// Get the number of the signal if the main process was killed by a
+    /// signal.
+    pub fn signal(&self) -> Option<i32> {
+        match self {
+            Self::Exited(_) => None,
+            Self::Signaled { signal, .. } => Some(*signal),
+        }
+    }
+}
+
+pub fn parse_result(s: &str) -> ServiceResult {
+    use ServiceResult::*;
+    match s {
+        "success" => Success,
+        "resources" => Resources,
+        "protocol" => Protocol,
+        "timeout" => Timeout,
+        "exit-code" => ExitCode,
+        "signal" => Signal,
+        "core-dump" => CoreDump,
+        "watchdog" => Watchdog,
+        "start-limit-hit" => StartLimitHit,
+        "oom-kill" => OomKill,
+        "exec-condition" => ExecCondition,
+        _ => Other(s.to_owned()),
+    }
+}
+
+pub fn parse_exit_status(code: i32, status: i32) -> Option<ExitStatus> {
+    // CLD_EXITED, CLD_KILLED, and CLD_DUMPED in <signal.h>.
+    match code {
+        1 => Some(ExitStatus::Exited(status)),
+        2 => Some(ExitStatus::Signaled {
+            signal: status,
+            core_dumped: false,
+        }),
+        3 => Some(ExitStatus::Signaled {
+            signal: status,
+            core_dumped: true,
+        }),
+        // The main process has never been started, or it's unknown.
+        _ => None,
+    }
+}
@@ -0,0 +1,110 @@
+use crate::{ExitStatus, FinishedRun, ServiceResult};
+use byte_unit::Byte;
+use std::time::Duration;
+
+/// Which time limit is exceeded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeLimit {
+    /// The wall-clock time limit, set with [RunSystem::runtime_max].
+    ///
+    /// [RunSystem::runtime_max]: crate::RunSystem::runtime_max
+    Wall,
+    /// The CPU time limit, set with [RunSystem::limit] and
+    /// [Rlimit::Cpu](crate::Rlimit::Cpu).
+    ///
+    /// [RunSystem::limit]: crate::RunSystem::limit
+    Cpu,
+}
+
+/// The verdict of a finished transient service, as an online judge would
+/// report it.
+///
+/// The verdict is classified with the [ServiceResult] and [ExitStatus] of
+/// the [FinishedRun], and the limits configured on the
+/// [RunSystem](crate::RunSystem) or [RunUser](crate::RunUser) producing
+/// it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    /// The service finished successfully.
+    Ok,
+    /// The service was terminated because it exceeded a time limit.
+    TimeLimitExceeded(TimeLimit),
+    /// The service was terminated because it exceeded the memory limit
+    /// set with [RunSystem::memory_max](crate::RunSystem::memory_max).
+    ///
+    /// A process other than the main process killed by the OOM killer is
+    /// only recognized if the `oom_kill` counter in `memory.events` is
+    /// recorded by [StartedRun::sample](crate::StartedRun::sample).
+    MemoryLimitExceeded,
+    /// The service was terminated by `SIGXFSZ` because it exceeded the
+    /// file size limit set with
+    /// [RunSystem::limit_fsize](crate::RunSystem::limit_fsize).
+    OutputLimitExceeded,
+    /// The service failed for another reason.  The [ExitStatus] is [None]
+    /// if the main process has never been started.
+    RuntimeError(Option<ExitStatus>),
+    /// The service was stopped by
+    /// [StartedRun::wait_timeout](crate::StartedRun::wait_timeout)
+    /// before it finished, so it can't be judged.
+    Aborted,
+}
+
+/// The limits configured on a [RunSystem](crate::RunSystem), used for
+/// classifying the [Verdict].
+#[derive(Debug, Default)]
+pub struct Limits {
+    pub limit_fsize_soft: Option<Byte>,
+    pub limit_cpu: Option<Duration>,
+    pub system_call_filter_kills: bool,
+}
+
+impl From<&FinishedRun> for Verdict {
+    fn from(run: &FinishedRun) -> Self {
+        let limits = &run.limits;
+        let signal = run.exit_status.and_then(|x| x.signal());
+
+        if run.aborted {
+            return Self::Aborted;
+        }
+
+        if run.result == ServiceResult::OomKill {
+            return Self::MemoryLimitExceeded;
+        }
+
+        if run.result == ServiceResult::Timeout {
+            return Self::TimeLimitExceeded(TimeLimit::Wall);
+        }
+
+        if signal == Some(libc::SIGXCPU) && limits.limit_cpu.is_some() {
+            return Self::TimeLimitExceeded(TimeLimit::Cpu);
+        }
+
+        // The kernel sends SIGKILL instead of SIGXCPU if the hard limit
+        // is reached, notably if the soft limit is same as the hard
+        // limit.  The CPU time of the unit is used to tell it from other
+        // SIGKILLs, although the limit is per process.
+        if let (Some(max), Some(usage)) = (limits.limit_cpu, run.cpu_time_usage) {
+            if signal == Some(libc::SIGKILL) && usage >= max {
+                return Self::TimeLimitExceeded(TimeLimit::Cpu);
+            }
+        }
+
+        if signal == Some(libc::SIGXFSZ) && limits.limit_fsize_soft.is_some() {
+            return Self::OutputLimitExceeded;
+        }
+
+        if !run.failed {
+            return Self::Ok;
+        }
+
+        // The OOM killer is not always recognized by systemd, for example
+        // if it kills a child process, so check the oom_kill counter in
+        // memory.events as well.  Reaching memory.max alone is not an
+        // MLE, as the kernel may reclaim memory and let the service go on.
+        if run.oom_kills.is_some_and(|n| n > 0) {
+            return Self::MemoryLimitExceeded;
+        }
+
+        Self::RuntimeError(run.exit_status)
+    }
+}
@@ -1,5 +1,7 @@
 use std::time::Duration;
-use systemd_run::RunUser;
+use systemd_run::{
+    ActiveState, ExitStatus, KillWho, RunUser, ServiceResult, TimeLimit, UnitEvent, Verdict,
+};
 
 #[async_std::test]
 async fn test_true() {
@@ -11,6 +13,9 @@ async fn test_true() {
         .await
         .expect("should be able to get the status of the Run");
     assert!(!r.is_failed(), "/bin/true should run successfully");
+    assert_eq!(r.service_result(), &ServiceResult::Success);
+    assert_eq!(r.exit_status(), Some(ExitStatus::Exited(0)));
+    assert_eq!(r.verdict(), Verdict::Ok);
 }
 
 #[async_std::test]
@@ -25,6 +30,12 @@ async fn test_false() {
         .await
         .expect("should be able to get the status of the Run");
     assert!(r.is_failed(), "/bin/false should fail");
+    assert_eq!(r.service_result(), &ServiceResult::ExitCode);
+    assert_eq!(r.exit_status(), Some(ExitStatus::Exited(1)));
+    assert_eq!(
+        r.verdict(),
+        Verdict::RuntimeError(Some(ExitStatus::Exited(1)))
+    );
 }
 
 #[async_std::test]
@@ -58,6 +69,8 @@ async fn test_runtime_max() {
         r.is_failed(),
         "/bin/sleep should have failed because of a timeout"
     );
+    assert_eq!(r.service_result(), &ServiceResult::Timeout);
+    assert_eq!(r.verdict(), Verdict::TimeLimitExceeded(TimeLimit::Wall));
     assert!(r.wall_time_usage() > Duration::from_secs(1));
     assert!(r.wall_time_usage() < Duration::from_secs(2));
 }
@@ -65,7 +78,7 @@ async fn test_runtime_max() {
 #[async_std::test]
 #[cfg(feature = "systemd_227")]
 async fn test_current_dir() {
-    const D: &'static str = concat!(env!("OUT_DIR"), "/test-aux/");
+    const D: &str = concat!(env!("OUT_DIR"), "/test-aux/");
     let r = RunUser::new("/bin/cat")
         .arg("stdin.txt")
         .current_dir(D)
@@ -81,7 +94,7 @@ async fn test_current_dir() {
 #[async_std::test]
 #[cfg(feature = "systemd_236")]
 async fn test_timeout_stop() {
-    const PATH: &'static str = concat!(env!("OUT_DIR"), "/test-aux/orga-itsuka");
+    const PATH: &str = concat!(env!("OUT_DIR"), "/test-aux/orga-itsuka");
     let r = RunUser::new(PATH)
         .runtime_max(Duration::from_millis(500))
         .timeout_stop(Duration::from_millis(500))
@@ -99,3 +112,194 @@ async fn test_timeout_stop() {
     assert!(r.wall_time_usage() > Duration::from_secs(1));
     assert!(r.wall_time_usage() < Duration::from_secs(2));
 }
+
+#[async_std::test]
+#[cfg(feature = "systemd_227")]
+async fn test_cpu_time_usage() {
+    let r = RunUser::new("/bin/sleep")
+        .arg("1")
+        .start()
+        .await
+        .expect("should be able to start /bin/sleep")
+        .wait()
+        .await
+        .expect("should be able to get the status of the Run");
+    assert!(!r.is_failed(), "/bin/sleep should run successfully");
+    let cpu = r
+        .cpu_time_usage()
+        .expect("CPU time usage should be available");
+    assert!(cpu < r.wall_time_usage(), "/bin/sleep should not burn CPU");
+    assert!(
+        r.cpu_user_time_usage().is_err(),
+        "the split should be unavailable without a sample"
+    );
+}
+
+#[async_std::test]
+#[cfg(feature = "unified_cgroup")]
+async fn test_cpu_user_system_time_usage() {
+    use futures::stream::TryStreamExt;
+    // Sleep after the loop, so it's finished before the last sample.
+    let run = RunUser::new("/bin/sh")
+        .args([
+            "-c",
+            "i=0; while [ $i -lt 100000 ]; do i=$((i+1)); done; sleep 1",
+        ])
+        .start()
+        .await
+        .expect("should be able to start /bin/sh");
+    run.monitor(Duration::from_millis(200))
+        .try_for_each(|_| async { Ok(()) })
+        .await
+        .expect("should be able to sample the Run");
+    let r = run
+        .wait()
+        .await
+        .expect("should be able to get the status of the Run");
+    assert!(!r.is_failed(), "/bin/sh should run successfully");
+    let user = r
+        .cpu_user_time_usage()
+        .expect("CPU user time usage should be available");
+    let system = r
+        .cpu_system_time_usage()
+        .expect("CPU system time usage should be available");
+    assert!(user > Duration::ZERO, "the loop should burn CPU");
+    assert!(user + system < r.wall_time_usage() * 2);
+}
+
+#[async_std::test]
+async fn test_wait_timeout() {
+    let r = RunUser::new("/bin/sleep")
+        .arg("10")
+        .start()
+        .await
+        .expect("should be able to start /bin/sleep")
+        .wait_timeout(Duration::from_secs(1))
+        .await
+        .expect("should be able to get the status of the Run");
+    assert!(r.is_aborted(), "/bin/sleep should have been stopped");
+    assert_eq!(r.verdict(), Verdict::Aborted);
+    assert!(r.wall_time_usage() >= Duration::from_secs(1));
+    assert!(r.wall_time_usage() < Duration::from_secs(10));
+}
+
+#[async_std::test]
+#[cfg(feature = "systemd_236")]
+async fn test_kill() {
+    use futures::stream::TryStreamExt;
+    let mut run = RunUser::new("/bin/sleep")
+        .arg("10")
+        .collect_on_fail()
+        .start()
+        .await
+        .expect("should be able to start /bin/sleep");
+    // Wait until systemd has really started the process.
+    {
+        let active = UnitEvent::ActiveState(ActiveState::Active);
+        let events = run.events();
+        futures::pin_mut!(events);
+        while let Some(ev) = events.try_next().await.expect("should get events") {
+            if ev == active {
+                break;
+            }
+        }
+    }
+    run.kill(libc::SIGKILL, KillWho::Main)
+        .await
+        .expect("should be able to kill /bin/sleep");
+    let r = run
+        .wait()
+        .await
+        .expect("should be able to get the status of the Run");
+    assert!(r.is_failed(), "/bin/sleep should have been killed");
+    assert_eq!(r.service_result(), &ServiceResult::Signal);
+    assert_eq!(
+        r.exit_status().and_then(|x| x.signal()),
+        Some(libc::SIGKILL)
+    );
+}
+
+#[async_std::test]
+#[cfg(feature = "systemd_236")]
+async fn test_kill_on_drop() {
+    const NAME: &str = "rust-systemd-run-test-kill-on-drop";
+    let run = RunUser::new("/bin/sleep")
+        .arg("10")
+        .service_name(NAME)
+        .collect_on_fail()
+        .start()
+        .await
+        .expect("should be able to start /bin/sleep")
+        .kill_on_drop();
+    async_std::task::sleep(Duration::from_millis(500)).await;
+    drop(run);
+    async_std::task::sleep(Duration::from_millis(500)).await;
+
+    // The unit should have been killed and collected, so the name can be
+    // used again.
+    let r = RunUser::new("/bin/true")
+        .service_name(NAME)
+        .start()
+        .await
+        .expect("should be able to reuse the name of the killed unit")
+        .wait()
+        .await
+        .expect("should be able to get the status of the Run");
+    assert!(!r.is_failed(), "/bin/true should run successfully");
+}
+
+#[async_std::test]
+async fn test_events() {
+    use futures::stream::TryStreamExt;
+    let mut run = RunUser::new("/bin/sleep")
+        .arg("1")
+        .start()
+        .await
+        .expect("should be able to start /bin/sleep");
+    let events: Vec<_> = run
+        .events()
+        .try_collect()
+        .await
+        .expect("should be able to get the events");
+    let active = events
+        .iter()
+        .position(|x| x == &UnitEvent::ActiveState(ActiveState::Active))
+        .expect("/bin/sleep should have been active");
+    let inactive = events
+        .iter()
+        .position(|x| x == &UnitEvent::ActiveState(ActiveState::Inactive))
+        .expect("/bin/sleep should have been inactive");
+    assert!(active < inactive);
+    assert!(events.iter().any(|x| matches!(x, UnitEvent::MainPid(_))));
+
+    let r = run
+        .wait()
+        .await
+        .expect("should be able to get the status of the Run");
+    assert!(!r.is_failed(), "/bin/sleep should run successfully");
+}
+
+#[async_std::test]
+#[cfg(feature = "systemd_227")]
+async fn test_monitor() {
+    use futures::stream::TryStreamExt;
+    let run = RunUser::new("/bin/sleep")
+        .arg("1")
+        .start()
+        .await
+        .expect("should be able to start /bin/sleep");
+    let samples: Vec<_> = run
+        .monitor(Duration::from_millis(200))
+        .try_collect()
+        .await
+        .expect("should be able to sample the Run");
+    assert!(
+        samples.iter().any(|x| x.tasks_current() == Some(1)),
+        "/bin/sleep should be sampled as one task"
+    );
+    let r = run
+        .wait()
+        .await
+        .expect("should be able to get the status of the Run");
+    assert!(!r.is_failed(), "/bin/sleep should run successfully");
+}
@@ -0,0 +1,63 @@
+use systemd_run::{CpuSet, Error};
+
+#[test]
+fn test_cpu_set_parse() {
+    let set: CpuSet = "0-3,8".parse().expect("should be a valid CPU list");
+    assert_eq!(set.iter().copied().collect::<Vec<_>>(), [0, 1, 2, 3, 8]);
+    assert_eq!(set.to_string(), "0-3,8");
+
+    let set: CpuSet = "5 1,2\n".parse().expect("should be a valid CPU list");
+    assert_eq!(set.to_string(), "1-2,5");
+
+    let mut set = CpuSet::new();
+    assert!(set.insert(CpuSet::MAX - 1));
+    assert!(!set.insert(CpuSet::MAX));
+    assert_eq!(set.to_string(), (CpuSet::MAX - 1).to_string());
+
+    let huge = format!("0-{}", usize::MAX);
+    for s in ["a", "3-1", "1-", "-1", "1-2-3", "8192", &huge] {
+        assert!(
+            matches!(s.parse::<CpuSet>(), Err(Error::InvalidCpuSet(_))),
+            "{:?} should be rejected",
+            s
+        );
+    }
+}
+
+#[async_std::test]
+#[cfg(feature = "systemd_236")]
+async fn test_cpu_affinity() {
+    use systemd_run::RunUser;
+
+    let set: CpuSet = "0".parse().unwrap();
+    let r = RunUser::new("/bin/true")
+        .cpu_affinity(&set)
+        .start()
+        .await
+        .expect("should be able to start /bin/true")
+        .wait()
+        .await
+        .expect("should be able to get the status of the Run");
+    assert!(!r.is_failed(), "/bin/true should run successfully on CPU 0");
+
+    // Rejected before reading the online CPUs.
+    let r = RunUser::new("/bin/true")
+        .cpu_affinity(&[usize::MAX])
+        .start()
+        .await;
+    assert!(matches!(r, Err(Error::InvalidCpuSet(_))));
+}
+
+#[async_std::test]
+#[cfg(feature = "systemd_244")]
+async fn test_numa_policy_empty() {
+    use systemd_run::{NumaPolicy, RunUser};
+
+    for p in [NumaPolicy::Bind, NumaPolicy::Interleave] {
+        let r = RunUser::new("/bin/true")
+            .numa_policy(p(CpuSet::new()))
+            .start()
+            .await;
+        assert!(matches!(r, Err(Error::InvalidCpuSet(_))));
+    }
+}
@@ -0,0 +1,45 @@
+use systemd_run::{Error, RunUser};
+
+#[async_std::test]
+async fn test_env() {
+    let r = RunUser::new("/bin/sh")
+        .args(["-c", "test \"$FOO\" = bar && test \"$BAR\" = 'a b'"])
+        .env("FOO", "baz")
+        .envs([("FOO", "bar"), ("BAR", "a b")])
+        .start()
+        .await
+        .expect("should be able to start /bin/sh")
+        .wait()
+        .await
+        .expect("should be able to get the status of the Run");
+    assert!(!r.is_failed(), "environment variables should be set");
+}
+
+#[async_std::test]
+#[cfg(feature = "systemd_236")]
+async fn test_env_remove() {
+    let r = RunUser::new("/bin/sh")
+        .args(["-c", "test -z \"${FOO+x}\""])
+        .env("FOO", "bar")
+        .env_remove("FOO")
+        .collect_on_fail()
+        .start()
+        .await
+        .expect("should be able to start /bin/sh")
+        .wait()
+        .await
+        .expect("should be able to get the status of the Run");
+    assert!(!r.is_failed(), "FOO should have been removed");
+}
+
+#[async_std::test]
+async fn test_env_invalid_key() {
+    for k in ["", "A=B", "A\0B"] {
+        let r = RunUser::new("/bin/true").env(k, "x").start().await;
+        assert!(
+            matches!(r, Err(Error::InvalidEnvironment(_))),
+            "{:?} should be rejected",
+            k
+        );
+    }
+}
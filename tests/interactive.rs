@@ -0,0 +1,27 @@
+#![cfg(feature = "systemd_236")]
+use systemd_run::{Interaction, RunUser};
+
+#[async_std::test]
+async fn test_interaction() {
+    const EXE: &str = concat!(env!("OUT_DIR"), "/test-aux/rw");
+    let (sol, int) = Interaction::user(RunUser::new(EXE).arg("w"), RunUser::new(EXE).arg("r"))
+        .run()
+        .await
+        .expect("should be able to run the interaction");
+    assert!(!sol.is_failed(), "'rw w' should have run successfully");
+    assert!(!int.is_failed(), "'rw r' should have read the output");
+}
+
+#[async_std::test]
+async fn test_interaction_both_ways() {
+    // The solution only answers if it gets the question, and the
+    // interactor only succeeds if it gets the answer.
+    let sol = RunUser::new("/bin/sh").args(["-c", "read x && [ \"$x\" = 41 ] && echo 42"]);
+    let int = RunUser::new("/bin/sh").args(["-c", "echo 41 && read y && [ \"$y\" = 42 ]"]);
+    let (sol, int) = Interaction::user(sol, int)
+        .run()
+        .await
+        .expect("should be able to run the interaction");
+    assert!(!sol.is_failed(), "the solution should have read 41");
+    assert!(!int.is_failed(), "the interactor should have read 42");
+}
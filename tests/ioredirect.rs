@@ -1,9 +1,10 @@
 #![cfg(feature = "systemd_236")]
+use futures::io::AsyncWriteExt;
 use systemd_run::{InputSpec, OutputSpec, RunUser};
 
 #[async_std::test]
 async fn test_all_null() {
-    const EXE: &'static str = concat!(env!("OUT_DIR"), "/test-aux/rw");
+    const EXE: &str = concat!(env!("OUT_DIR"), "/test-aux/rw");
     let r = RunUser::new(EXE)
         .arg("r")
         .stdin(InputSpec::null())
@@ -24,8 +25,8 @@ async fn test_all_null() {
 
 #[async_std::test]
 async fn test_stdin_file() {
-    const EXE: &'static str = concat!(env!("OUT_DIR"), "/test-aux/rw");
-    const DATA: &'static str = concat!(env!("OUT_DIR"), "/test-aux/stdin.txt");
+    const EXE: &str = concat!(env!("OUT_DIR"), "/test-aux/rw");
+    const DATA: &str = concat!(env!("OUT_DIR"), "/test-aux/stdin.txt");
     let r = RunUser::new(EXE)
         .arg("r")
         .stdin(InputSpec::file(DATA))
@@ -37,3 +38,69 @@ async fn test_stdin_file() {
         .expect("should be able to get the status of the Run");
     assert!(!r.is_failed(), "'rw r' should have run successfully");
 }
+
+#[async_std::test]
+async fn test_stdout_pipe() {
+    const EXE: &str = concat!(env!("OUT_DIR"), "/test-aux/rw");
+    for (cap, expected, truncated) in [
+        (None, &b"1145141919810"[..], false),
+        (Some(4), &b"1145"[..], true),
+    ] {
+        let mut run = RunUser::new(EXE)
+            .arg("w")
+            .stdout(OutputSpec::pipe())
+            .start()
+            .await
+            .expect("should be able to start test program rw");
+        let stdout = run.take_stdout().expect("stdout should be a pipe");
+        let (out, r) = futures::join!(stdout.capture(cap), run.wait());
+        let out = out.expect("should be able to read the output");
+        let r = r.expect("should be able to get the status of the Run");
+        assert!(!r.is_failed(), "'rw w' should have run successfully");
+        assert_eq!(out.data(), expected);
+        assert_eq!(out.is_truncated(), truncated);
+    }
+}
+
+#[async_std::test]
+async fn test_stdin_pipe() {
+    const EXE: &str = concat!(env!("OUT_DIR"), "/test-aux/rw");
+    let mut run = RunUser::new(EXE)
+        .arg("r")
+        .stdin(InputSpec::pipe())
+        .start()
+        .await
+        .expect("should be able to start test program rw");
+    let mut stdin = run.take_stdin().expect("stdin should be a pipe");
+    stdin
+        .write_all(b"1145141919810\n")
+        .await
+        .expect("should be able to write the input");
+    drop(stdin);
+    let r = run
+        .wait()
+        .await
+        .expect("should be able to get the status of the Run");
+    assert!(!r.is_failed(), "'rw r' should have run successfully");
+}
+
+#[async_std::test]
+async fn test_stdin_data() {
+    const EXE: &str = concat!(env!("OUT_DIR"), "/test-aux/rw");
+    let r = RunUser::new(EXE)
+        .arg("r")
+        .stdin(InputSpec::data("1145141919810\n").unwrap())
+        .start()
+        .await
+        .expect("should be able to start test program rw")
+        .wait()
+        .await
+        .expect("should be able to get the status of the Run");
+    assert!(!r.is_failed(), "'rw r' should have run successfully");
+}
+
+#[test]
+fn test_stdin_data_too_large() {
+    let data = vec![0u8; 64 * 1024 * 1024 + 1];
+    assert!(InputSpec::data(data).is_none());
+}
@@ -0,0 +1,63 @@
+#![cfg(feature = "systemd_236")]
+use std::time::Duration;
+use systemd_run::{Error, OutputSpec, RunUser};
+
+#[async_std::test]
+async fn test_journal() {
+    const EXE: &str = concat!(env!("OUT_DIR"), "/test-aux/rw");
+    let r = RunUser::new(EXE)
+        .arg("w")
+        .stdout(OutputSpec::journal())
+        .syslog_identifier("rust-systemd-run-test")
+        .log_extra_fields([("RUST_SYSTEMD_RUN_TEST", "1")])
+        .start()
+        .await
+        .expect("should be able to start test program rw")
+        .wait()
+        .await
+        .expect("should be able to get the status of the Run");
+    assert!(!r.is_failed(), "'rw w' should have run successfully");
+    assert!(r.invocation_id().is_some());
+
+    // The journal may be a little behind.
+    let mut entries = vec![];
+    for _ in 0..20 {
+        entries = r
+            .journal()
+            .await
+            .expect("should be able to read the journal");
+        if !entries.is_empty() {
+            break;
+        }
+        async_std::task::sleep(Duration::from_millis(100)).await;
+    }
+    assert!(
+        !entries.is_empty(),
+        "the entries of the RunUser unit should be found"
+    );
+
+    let e = entries
+        .iter()
+        .find(|e| e.message().as_deref() == Some("1145141919810"))
+        .expect("the output should be in the journal");
+    assert_eq!(
+        e.field("SYSLOG_IDENTIFIER"),
+        Some(&b"rust-systemd-run-test"[..])
+    );
+    assert_eq!(e.field("RUST_SYSTEMD_RUN_TEST"), Some(&b"1"[..]));
+}
+
+#[async_std::test]
+async fn test_log_extra_fields_invalid() {
+    for k in ["", "_PID", "0A", "lower"] {
+        let r = RunUser::new("/bin/true")
+            .log_extra_fields([(k, "x")])
+            .start()
+            .await;
+        assert!(
+            matches!(r, Err(Error::InvalidLogField(_))),
+            "{:?} should be rejected",
+            k
+        );
+    }
+}
@@ -0,0 +1,39 @@
+use systemd_run::{Manager, RunUser};
+
+#[async_std::test]
+async fn test_reuse_manager() {
+    let manager = Manager::user()
+        .await
+        .expect("should be able to connect to the per-user manager");
+    let mut runs = vec![];
+    for _ in 0..4 {
+        let run = manager
+            .start_user(RunUser::new("/bin/true"))
+            .await
+            .expect("should be able to start /bin/true");
+        runs.push(run);
+    }
+    for run in runs {
+        let r = run
+            .wait()
+            .await
+            .expect("should be able to get the status of the Run");
+        assert!(!r.is_failed(), "/bin/true should run successfully");
+    }
+}
+
+#[async_std::test]
+async fn test_private_manager() {
+    let dir = std::env::var("XDG_RUNTIME_DIR").expect("XDG_RUNTIME_DIR should be set");
+    let manager = Manager::private(format!("unix:path={}/systemd/private", dir))
+        .await
+        .expect("should be able to connect to the private socket");
+    let r = manager
+        .start_user(RunUser::new("/bin/true"))
+        .await
+        .expect("should be able to start /bin/true")
+        .wait()
+        .await
+        .expect("should be able to get the status of the Run");
+    assert!(!r.is_failed(), "/bin/true should run successfully");
+}
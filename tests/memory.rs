@@ -2,8 +2,8 @@
 mod memory_tests_need_unified_cgroup {
     use byte_unit::Byte;
     use byte_unit::Unit::MiB;
-    use systemd_run::RunUser;
-    const PATH: &'static str = concat!(env!("OUT_DIR"), "/test-aux/memory");
+    use systemd_run::{ExitStatus, MemoryLimit, OomPolicy, RunUser, ServiceResult, Verdict};
+    const PATH: &str = concat!(env!("OUT_DIR"), "/test-aux/memory");
 
     #[async_std::test]
     async fn test_memory_ok() {
@@ -22,6 +22,53 @@ mod memory_tests_need_unified_cgroup {
         );
     }
 
+    #[async_std::test]
+    #[cfg(feature = "systemd_247")]
+    async fn test_memory_max_infinity_percent() {
+        for lim in [MemoryLimit::infinity(), MemoryLimit::percent(100).unwrap()] {
+            let r = RunUser::new(PATH)
+                .memory_max(lim)
+                .memory_high(lim)
+                .memory_low(Byte::from(0usize))
+                .oom_policy(OomPolicy::Kill)
+                .start()
+                .await
+                .unwrap()
+                .wait()
+                .await
+                .unwrap();
+            assert!(!r.is_failed(), "allocating 256 MB should be fine w/o limit");
+        }
+        assert!(MemoryLimit::percent(101).is_none());
+    }
+
+    #[async_std::test]
+    async fn test_memory_peak() {
+        use futures::stream::TryStreamExt;
+        use std::time::Duration;
+        // Without MemoryPeak (systemd 255), the peak is only recorded by
+        // the samples, so keep the service running for a while after the
+        // allocation.
+        let run = RunUser::new("/bin/sh")
+            .args(["-c", &format!("{} && sleep 1", PATH)])
+            .memory_max(Byte::from_i64_with_unit(384, MiB).unwrap())
+            .memory_swap_max(Byte::from(0usize))
+            .start()
+            .await
+            .unwrap();
+        run.monitor(Duration::from_millis(200))
+            .try_for_each(|_| async { Ok(()) })
+            .await
+            .unwrap();
+        let r = run.wait().await.unwrap();
+        assert!(!r.is_failed());
+        let peak = r.memory_peak().expect("memory peak should be available");
+        assert!(
+            peak >= Byte::from_i64_with_unit(256, MiB).unwrap(),
+            "the peak should include the 256 MB allocated"
+        );
+    }
+
     #[async_std::test]
     #[cfg(feature = "systemd_236")]
     async fn test_memory_limit_exceed() {
@@ -39,6 +86,71 @@ mod memory_tests_need_unified_cgroup {
             r.is_failed(),
             "allocating 256 MB should fail with MemoryMax=128MB"
         );
+        assert_eq!(
+            r.service_result(),
+            &ServiceResult::OomKill,
+            "the process should have been killed by the OOM killer"
+        );
+        assert_eq!(r.verdict(), Verdict::MemoryLimitExceeded);
+    }
+
+    #[async_std::test]
+    #[cfg(feature = "systemd_244")]
+    async fn test_memory_limit_exceed_child() {
+        use futures::stream::TryStreamExt;
+        use std::time::Duration;
+        // The shell only sees its child killed, so systemd does not
+        // report oom-kill.  The verdict should come from the oom_kill
+        // counter in memory.events, which is recorded by the samples.
+        let run = RunUser::new("/bin/sh")
+            .args(["-c", &format!("{} || {{ sleep 1; exit 1; }}", PATH)])
+            .memory_max(Byte::from_i64_with_unit(128, MiB).unwrap())
+            .memory_swap_max(Byte::from(0usize))
+            .oom_policy(OomPolicy::Continue)
+            .collect_on_fail()
+            .start()
+            .await
+            .unwrap();
+        run.monitor(Duration::from_millis(200))
+            .try_for_each(|_| async { Ok(()) })
+            .await
+            .unwrap();
+        let r = run.wait().await.unwrap();
+        assert!(r.is_failed(), "/bin/sh should exit with 1");
+        assert_eq!(r.verdict(), Verdict::MemoryLimitExceeded);
+    }
+
+    #[async_std::test]
+    #[cfg(feature = "systemd_236")]
+    async fn test_memory_limit_reclaim() {
+        use futures::stream::TryStreamExt;
+        use std::time::Duration;
+        // Writing a file larger than MemoryMax fills the page cache up to
+        // the limit, but it's reclaimed instead of invoking the OOM
+        // killer.  The failure is not an MLE.
+        const FILE: &str = concat!(env!("OUT_DIR"), "/memory-reclaim.tmp");
+        let script = format!(
+            "dd if=/dev/zero of={0} bs=1M count=256; rm -f {0}; sleep 1; exit 1",
+            FILE
+        );
+        let run = RunUser::new("/bin/sh")
+            .args(["-c", &script])
+            .memory_max(Byte::from_i64_with_unit(64, MiB).unwrap())
+            .memory_swap_max(Byte::from(0usize))
+            .collect_on_fail()
+            .start()
+            .await
+            .unwrap();
+        run.monitor(Duration::from_millis(200))
+            .try_for_each(|_| async { Ok(()) })
+            .await
+            .unwrap();
+        let r = run.wait().await.unwrap();
+        assert!(r.is_failed(), "/bin/sh should exit with 1");
+        assert_eq!(
+            r.verdict(),
+            Verdict::RuntimeError(Some(ExitStatus::Exited(1)))
+        );
     }
 
     #[async_std::test]
@@ -46,7 +158,7 @@ mod memory_tests_need_unified_cgroup {
     async fn test_slice_memory_limit_exceed() {
         // Create a slice with "unique" name.  I generated it locally with
         // uuidgen.
-        const SLICE: &'static str = "7772d908_2631_4b34_aba0_20454e89cf9a.slice";
+        const SLICE: &str = "7772d908_2631_4b34_aba0_20454e89cf9a.slice";
         let path = std::path::PathBuf::from(env!("XDG_RUNTIME_DIR")).join("systemd/user");
 
         std::fs::create_dir_all(&path).unwrap();
@@ -70,7 +182,7 @@ mod memory_tests_need_unified_cgroup {
 
         // clean up
         RunUser::new("/usr/bin/systemctl")
-            .args(&["--user", "stop", SLICE])
+            .args(["--user", "stop", SLICE])
             .collect_on_fail()
             .start()
             .await
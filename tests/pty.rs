@@ -0,0 +1,42 @@
+#![cfg(feature = "systemd_236")]
+use futures::io::AsyncReadExt;
+use systemd_run::RunUser;
+
+#[async_std::test]
+async fn test_pty() {
+    const EXE: &str = concat!(env!("OUT_DIR"), "/test-aux/rw");
+    let mut run = RunUser::new(EXE)
+        .arg("w")
+        .pty()
+        .start()
+        .await
+        .expect("should be able to start test program rw");
+    let mut pty = run.take_pty().expect("should have a pty");
+    pty.set_window_size(24, 80)
+        .expect("should be able to set the window size");
+    assert_eq!(pty.window_size().unwrap(), (24, 80));
+
+    let mut out = vec![];
+    let (n, r) = futures::join!(pty.read_to_end(&mut out), run.wait());
+    n.expect("should be able to read from the pty");
+    let r = r.expect("should be able to get the status of the Run");
+    assert!(!r.is_failed(), "'rw w' should have run successfully");
+    assert_eq!(out, b"1145141919810");
+}
+
+#[async_std::test]
+async fn test_pty_controlling_terminal() {
+    // Opening /dev/tty fails without a controlling terminal.
+    let mut run = RunUser::new("/bin/sh")
+        .args(["-c", "exec 3</dev/tty"])
+        .pty()
+        .start()
+        .await
+        .expect("should be able to start /bin/sh");
+    let mut pty = run.take_pty().expect("should have a pty");
+    let mut out = vec![];
+    let (n, r) = futures::join!(pty.read_to_end(&mut out), run.wait());
+    n.expect("should be able to read from the pty");
+    let r = r.expect("should be able to get the status of the Run");
+    assert!(!r.is_failed(), "the pty should be the controlling terminal");
+}
@@ -2,11 +2,11 @@
 
 use byte_unit::Byte;
 use byte_unit::Unit::MiB;
-use systemd_run::{RunSystem, RunUser};
+use systemd_run::{Rlimit, RunSystem, RunUser, TimeLimit, Verdict};
 
 #[async_std::test]
 async fn test_limit_fsize() {
-    const F: &'static str = concat!(env!("OUT_DIR"), "/test-aux/test-fsz");
+    const F: &str = concat!(env!("OUT_DIR"), "/test-aux/test-fsz");
     // Attempt to copy 4M, but use limit_fsize = 1M to stop it.
     let lim = Byte::from_i64_with_unit(1, MiB).unwrap();
     let r = RunUser::new("/bin/dd")
@@ -23,6 +23,7 @@ async fn test_limit_fsize() {
         .await
         .expect("should be able to get the status of the Run");
     assert!(r.is_failed(), "dd shouldn't be able to finish the write");
+    assert_eq!(r.verdict(), Verdict::OutputLimitExceeded);
 
     let f = std::fs::File::open(F).expect("output file should exist");
     use std::os::unix::fs::MetadataExt;
@@ -30,9 +31,44 @@ async fn test_limit_fsize() {
     assert!(Byte::from_u64(meta.size()) <= lim);
 }
 
+#[async_std::test]
+async fn test_limit_cpu() {
+    for soft in [1, 2] {
+        let r = RunUser::new("/bin/sh")
+            .args(["-c", "while :; do :; done"])
+            .limit(Rlimit::Cpu, soft, 2)
+            .runtime_max(std::time::Duration::from_secs(10))
+            .collect_on_fail()
+            .start()
+            .await
+            .expect("should be able to start sh")
+            .wait()
+            .await
+            .expect("should be able to get the status of the Run");
+        assert!(r.is_failed(), "sh should be killed with the CPU limit");
+        assert_eq!(r.verdict(), Verdict::TimeLimitExceeded(TimeLimit::Cpu));
+    }
+}
+
+#[async_std::test]
+async fn test_sigxcpu_without_limit_cpu() {
+    // SIGXCPU sent by someone else is not a TLE without the CPU limit.
+    let r = RunUser::new("/bin/sh")
+        .args(["-c", "kill -XCPU $$"])
+        .collect_on_fail()
+        .start()
+        .await
+        .expect("should be able to start sh")
+        .wait()
+        .await
+        .expect("should be able to get the status of the Run");
+    assert!(r.is_failed(), "sh should be killed by SIGXCPU");
+    assert!(matches!(r.verdict(), Verdict::RuntimeError(Some(_))));
+}
+
 #[async_std::test]
 async fn test_limit_nofile() {
-    const E: &'static str = concat!(env!("OUT_DIR"), "/test-aux/waste-fd");
+    const E: &str = concat!(env!("OUT_DIR"), "/test-aux/waste-fd");
     let r = RunUser::new(E)
         .limit_nofile(16.try_into().unwrap())
         .collect_on_fail()
@@ -51,7 +87,7 @@ async fn test_root_limit_stack() {
     // Unfortunately, in some environments (notably, GitHub runners) the
     // hard limit of stack is set to a finite value (likely same as the soft
     // limit).  So we have to run this as root to ensure it working.
-    const E: &'static str = concat!(env!("OUT_DIR"), "/test-aux/use-stack");
+    const E: &str = concat!(env!("OUT_DIR"), "/test-aux/use-stack");
     let lim = Byte::from_i64_with_unit(256, MiB).unwrap();
     let r = RunSystem::new(E)
         .limit_stack(lim)
@@ -67,7 +103,7 @@ async fn test_root_limit_stack() {
 #[async_std::test]
 #[ignore]
 async fn test_root_limit_nproc() {
-    const E: &'static str = concat!(env!("OUT_DIR"), "/test-aux/waste-pid");
+    const E: &str = concat!(env!("OUT_DIR"), "/test-aux/waste-pid");
     // Use dynamic() here so the test will be irrelevant to any other users,
     // as RLIM_NPROC accounts all PIDs for a user.  Set runtime_max()
     // because some implementations may dead lock when PID is exhausted.
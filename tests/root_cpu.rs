@@ -6,7 +6,7 @@ use systemd_run::{Identity, RunSystem};
 #[cfg(feature = "systemd_244")]
 #[cfg(feature = "unified_cgroup")]
 async fn test_root_allowed_cpus() {
-    const PATH: &'static str = concat!(env!("OUT_DIR"), "/test-aux/threads");
+    const PATH: &str = concat!(env!("OUT_DIR"), "/test-aux/threads");
     let r = RunSystem::new(PATH)
         .allowed_cpus(&[0])
         .identity(Identity::user_group("nobody", "nogroup"))
@@ -28,7 +28,7 @@ async fn test_root_allowed_cpus() {
 #[ignore]
 #[cfg(feature = "systemd_213")]
 async fn test_root_cpu_quota() {
-    const PATH: &'static str = concat!(env!("OUT_DIR"), "/test-aux/threads");
+    const PATH: &str = concat!(env!("OUT_DIR"), "/test-aux/threads");
     let r = RunSystem::new(PATH)
         .cpu_quota(std::num::NonZeroU64::new(100).unwrap())
         .identity(Identity::user_group("nobody", "nogroup"))
@@ -50,3 +50,31 @@ async fn test_root_cpu_quota() {
         "test program should run for about 1s with 100% CPU quota"
     );
 }
+
+#[async_std::test]
+#[ignore]
+#[cfg(feature = "systemd_244")]
+async fn test_root_cpu_quota_fraction() {
+    use std::num::NonZeroU64;
+    const PATH: &str = concat!(env!("OUT_DIR"), "/test-aux/threads");
+    let one = NonZeroU64::new(1).unwrap();
+    for run in [
+        RunSystem::new(PATH).cpu_quota_permille(NonZeroU64::new(1000).unwrap()),
+        RunSystem::new(PATH).cpu_quota_fraction(one, one),
+    ] {
+        let r = run
+            .cpu_quota_period(Duration::from_millis(10))
+            .identity(Identity::user_group("nobody", "nogroup"))
+            .start()
+            .await
+            .expect("should be able to start the test program")
+            .wait()
+            .await
+            .expect("should be able to get the status of the Run");
+        assert!(!r.is_failed(), "test program should exit normally");
+        assert!(
+            r.wall_time_usage() >= Duration::from_millis(900),
+            "test program should run for at least 0.9s with one CPU quota"
+        );
+    }
+}
@@ -1,11 +1,11 @@
 use std::fs::read_to_string;
-use systemd_run::{CpuScheduling, OutputSpec, RunSystem};
+use systemd_run::{CpuScheduling, IoScheduling, OutputSpec, RunSystem};
 
 #[ignore]
 #[cfg(feature = "systemd_252")]
 #[async_std::test]
 async fn test_root_cpu_sched() {
-    const PATH: &'static str = concat!(env!("OUT_DIR"), "/test-aux/sched-1");
+    const PATH: &str = concat!(env!("OUT_DIR"), "/test-aux/sched-1");
     let sched = CpuScheduling::round_robin(42.try_into().unwrap());
     let r = RunSystem::new("/usr/bin/chrt")
         .arg("-p")
@@ -29,7 +29,7 @@ async fn test_root_cpu_sched() {
 #[ignore]
 #[async_std::test]
 async fn test_root_cpu_sched_default_priority() {
-    const PATH: &'static str = concat!(env!("OUT_DIR"), "/test-aux/sched-2");
+    const PATH: &str = concat!(env!("OUT_DIR"), "/test-aux/sched-2");
     let sched = CpuScheduling::round_robin_default_priority();
     let r = RunSystem::new("/usr/bin/chrt")
         .arg("-p")
@@ -48,3 +48,40 @@ async fn test_root_cpu_sched_default_priority() {
 
     assert!(content.contains("SCHED_RR\n"), "wrong schedule policy");
 }
+
+#[ignore]
+#[async_std::test]
+async fn test_root_nice_io_sched() {
+    const PATH: &str = concat!(env!("OUT_DIR"), "/test-aux/sched-3");
+    let sched = CpuScheduling::batch().nice(7).unwrap();
+    let io_sched = IoScheduling::best_effort(6).unwrap();
+    let r = RunSystem::new("/bin/sh")
+        .arg("-c")
+        .arg("nice; ionice")
+        .stdout(OutputSpec::file(PATH))
+        .cpu_schedule(sched)
+        .io_schedule(io_sched)
+        .start()
+        .await
+        .expect("should be able to start /bin/sh")
+        .wait()
+        .await
+        .expect("should be able to get the status of the Run");
+    assert!(!r.is_failed(), "nice and ionice should run successfully");
+
+    let content = read_to_string(PATH).expect("should be able to read nice and ionice output");
+
+    assert!(content.starts_with("7\n"), "wrong nice level");
+    assert!(
+        content.contains("best-effort: prio 6"),
+        "wrong IO scheduling"
+    );
+}
+
+#[test]
+fn test_sched_priority_range() {
+    assert!(CpuScheduling::default().nice(-20).is_some());
+    assert!(CpuScheduling::default().nice(20).is_none());
+    assert!(IoScheduling::realtime(7).is_some());
+    assert!(IoScheduling::best_effort(8).is_none());
+}
@@ -78,3 +78,32 @@ async fn test_root_nobody_access() {
         f
     );
 }
+
+#[async_std::test]
+#[ignore]
+#[cfg(feature = "systemd_236")]
+async fn test_root_capabilities() {
+    use systemd_run::{Capability, OutputSpec};
+
+    const PATH: &str = concat!(env!("OUT_DIR"), "/test-aux/caps-1");
+    let r = RunSystem::new("/bin/grep")
+        .arg("^Cap\\(Amb\\|Bnd\\)")
+        .arg("/proc/self/status")
+        .stdout(OutputSpec::file(PATH))
+        .identity(Identity::user_group("nobody", "nogroup"))
+        .capability_bounding_set([Capability::SysPtrace, Capability::Kill])
+        .ambient_capabilities([Capability::SysPtrace])
+        .start()
+        .await
+        .expect("should be able to start /bin/grep")
+        .wait()
+        .await
+        .expect("should be able to get the status of the Run");
+    assert!(!r.is_failed(), "grep should run successfully");
+
+    let content = std::fs::read_to_string(PATH).expect("should be able to read grep output");
+
+    // CAP_SYS_PTRACE is 1 << 19, and CAP_KILL is 1 << 5.
+    assert!(content.contains("CapBnd:\t0000000000080020\n"));
+    assert!(content.contains("CapAmb:\t0000000000080000\n"));
+}
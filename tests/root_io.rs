@@ -0,0 +1,61 @@
+#![cfg(feature = "unified_cgroup")]
+use byte_unit::Byte;
+use byte_unit::Unit::MiB;
+use futures::stream::TryStreamExt;
+use std::time::Duration;
+use systemd_run::RunSystem;
+
+#[async_std::test]
+#[ignore]
+async fn test_root_io_limits() {
+    let bw = Byte::from_i64_with_unit(16, MiB).unwrap();
+    let r = RunSystem::new("/bin/dd")
+        .args(["if=/dev/zero", "of=/dev/null", "bs=4096", "count=1024"])
+        .io_weight(50)
+        .io_read_bandwidth_max("/", bw)
+        .io_write_bandwidth_max("/", bw)
+        .io_read_iops_max("/", 1000)
+        .io_write_iops_max("/", 1000)
+        .start()
+        .await
+        .expect("should be able to start dd with IO limits")
+        .wait()
+        .await
+        .expect("should be able to get the status of the Run");
+    assert!(!r.is_failed(), "dd should run successfully");
+}
+
+#[async_std::test]
+#[ignore]
+async fn test_root_io_bytes() {
+    // Direct IO bypasses the page cache, so the block device is really
+    // accessed.  /tmp may be a tmpfs, so use the build directory.  The
+    // service manager may drop the counters once the service is stopped,
+    // so sleep for a sample to record them.
+    const PATH: &str = concat!(env!("OUT_DIR"), "/io-bytes.tmp");
+    let script = format!(
+        "dd if=/dev/zero of={0} bs=1M count=4 oflag=direct && \
+         dd if={0} of=/dev/null bs=1M iflag=direct && sleep 1",
+        PATH
+    );
+    let run = RunSystem::new("/bin/sh")
+        .args(["-c", &script])
+        .start()
+        .await
+        .expect("should be able to start dd");
+    run.monitor(Duration::from_millis(200))
+        .try_for_each(|_| async { Ok(()) })
+        .await
+        .expect("should be able to sample the Run");
+    let r = run
+        .wait()
+        .await
+        .expect("should be able to get the status of the Run");
+    let _ = std::fs::remove_file(PATH);
+    assert!(!r.is_failed(), "dd should run successfully");
+    let size = Byte::from_i64_with_unit(4, MiB).unwrap();
+    let read = r.io_read_bytes().expect("IO read should be available");
+    let write = r.io_write_bytes().expect("IO write should be available");
+    assert!(read >= size, "dd should have read 4 MiB");
+    assert!(write >= size, "dd should have written 4 MiB");
+}
@@ -4,7 +4,7 @@ use systemd_run::{Identity, RunSystem};
 #[ignore]
 #[cfg(feature = "systemd_227")]
 async fn test_root_no_new_priv() {
-    const PATH: &'static str = concat!(env!("OUT_DIR"), "/test-aux/nosgid");
+    const PATH: &str = concat!(env!("OUT_DIR"), "/test-aux/nosgid");
     let r = RunSystem::new(PATH)
         .identity(Identity::user_group("nobody", "nogroup"))
         .no_new_privileges()
@@ -22,7 +22,7 @@ async fn test_root_no_new_priv() {
 #[cfg(feature = "systemd_247")]
 async fn test_root_protect_proc() {
     let r = RunSystem::new("/bin/test")
-        .args(&["-e", "/proc/1"])
+        .args(["-e", "/proc/1"])
         .identity(Identity::dynamic())
         .protect_proc(systemd_run::ProtectProc::invisible())
         .start()
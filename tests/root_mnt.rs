@@ -5,7 +5,7 @@ use systemd_run::{Mount, RunSystem};
 #[ignore]
 #[cfg(feature = "systemd_233")]
 async fn test_root_mnt_bind_minimal() {
-    const PATH: &'static str = concat!(env!("OUT_DIR"), "/test-aux");
+    const PATH: &str = concat!(env!("OUT_DIR"), "/test-aux");
     let r = RunSystem::new("/minimal")
         .mount("/", Mount::bind(PATH))
         .start()
@@ -19,7 +19,7 @@ async fn test_root_mnt_bind_minimal() {
 
 #[cfg(feature = "systemd_236")]
 async fn test_root_mnt_w(f: fn() -> Mount) {
-    const EXE: &'static str = concat!(env!("OUT_DIR"), "/test-aux/rw");
+    const EXE: &str = concat!(env!("OUT_DIR"), "/test-aux/rw");
 
     // Write something into the mount.
     let r = RunSystem::new(EXE)
@@ -51,7 +51,7 @@ async fn test_root_mnt_w(f: fn() -> Mount) {
 
 #[cfg(feature = "systemd_236")]
 async fn test_root_mnt(f: fn() -> Mount) {
-    const EXE: &'static str = concat!(env!("OUT_DIR"), "/test-aux/rw");
+    const EXE: &str = concat!(env!("OUT_DIR"), "/test-aux/rw");
     test_root_mnt_w(f).await;
 
     // Read the content back.
@@ -79,7 +79,7 @@ async fn test_root_mnt(f: fn() -> Mount) {
 #[ignore]
 #[cfg(feature = "systemd_247")]
 async fn test_root_mnt_image() {
-    const IMG: &'static str = concat!(env!("OUT_DIR"), "/test-aux/floppy.img");
+    const IMG: &str = concat!(env!("OUT_DIR"), "/test-aux/floppy.img");
 
     // Create a floppy-like image first
     let r = RunSystem::new("/bin/dd")
@@ -112,7 +112,7 @@ async fn test_root_mnt_image() {
 #[ignore]
 #[cfg(feature = "systemd_236")]
 async fn test_root_mnt_bind() {
-    const BIND: &'static str = concat!(env!("OUT_DIR"), "/test-aux");
+    const BIND: &str = concat!(env!("OUT_DIR"), "/test-aux");
     test_root_mnt(|| Mount::bind(BIND)).await;
 }
 
@@ -120,7 +120,7 @@ async fn test_root_mnt_bind() {
 #[ignore]
 #[cfg(feature = "systemd_238")]
 async fn test_root_mnt_tmpfs() {
-    test_root_mnt_w(|| Mount::tmpfs()).await;
+    test_root_mnt_w(Mount::tmpfs).await;
 }
 
 #[async_std::test]
@@ -45,7 +45,7 @@ async fn test_root_private_network_wget() {
 #[ignore]
 #[cfg(feature = "systemd_249")]
 async fn test_root_private_ipc() {
-    const PATH: &'static str = concat!(env!("OUT_DIR"), "/test-aux/shm");
+    const PATH: &str = concat!(env!("OUT_DIR"), "/test-aux/shm");
     // Run twice, if IPC namespace seperation is not in-effect the second
     // run will fail.
     for _ in 0..2 {
@@ -69,7 +69,7 @@ async fn test_root_private_ipc() {
 #[ignore]
 #[cfg(feature = "systemd_232")]
 async fn test_root_private_users() {
-    const PATH: &'static str = concat!(env!("OUT_DIR"), "/test-aux/setuid");
+    const PATH: &str = concat!(env!("OUT_DIR"), "/test-aux/setuid");
     let r = RunSystem::new(PATH)
         .private_users()
         .start()
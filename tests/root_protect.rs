@@ -0,0 +1,90 @@
+#![cfg(feature = "systemd_236")]
+use systemd_run::{AccessPath, Error, ProtectHome, ProtectSystem, RunSystem};
+
+#[async_std::test]
+#[ignore]
+async fn test_root_protect_system_strict() {
+    let r = RunSystem::new("/bin/touch")
+        .arg("/var/tmp/rust-systemd-run-protect")
+        .protect_system(ProtectSystem::Strict)
+        .collect_on_fail()
+        .start()
+        .await
+        .expect("should be able to start /bin/touch")
+        .wait()
+        .await
+        .expect("should be able to get the status of the Run");
+    assert!(r.is_failed(), "/var/tmp should be read-only");
+
+    let r = RunSystem::new("/bin/touch")
+        .arg("/var/tmp/rust-systemd-run-protect")
+        .protect_system(ProtectSystem::Strict)
+        .read_write_paths(["/var/tmp"])
+        .start()
+        .await
+        .expect("should be able to start /bin/touch")
+        .wait()
+        .await
+        .expect("should be able to get the status of the Run");
+    assert!(!r.is_failed(), "/var/tmp should be writable");
+    let _ = std::fs::remove_file("/var/tmp/rust-systemd-run-protect");
+}
+
+#[async_std::test]
+#[ignore]
+async fn test_root_inaccessible_paths() {
+    let r = RunSystem::new("/bin/ls")
+        .arg("/root")
+        .protect_home(ProtectHome::ReadOnly)
+        .inaccessible_paths([
+            AccessPath::new("/root"),
+            AccessPath::new("/nonexist/rust-systemd-run").ignore_nonexist(),
+        ])
+        .collect_on_fail()
+        .start()
+        .await
+        .expect("should be able to start /bin/ls")
+        .wait()
+        .await
+        .expect("should be able to get the status of the Run");
+    assert!(r.is_failed(), "/root should be inaccessible");
+}
+
+#[async_std::test]
+#[ignore]
+async fn test_root_access_path_space() {
+    const DIR: &str = "/var/tmp/rust systemd-run protect";
+    std::fs::create_dir_all(DIR).expect("should be able to create the directory");
+    let r = RunSystem::new("/bin/touch")
+        .arg(format!("{}/file", DIR))
+        .read_only_paths([DIR])
+        .collect_on_fail()
+        .start()
+        .await
+        .expect("should be able to start /bin/touch")
+        .wait()
+        .await
+        .expect("should be able to get the status of the Run");
+    let _ = std::fs::remove_dir_all(DIR);
+    assert!(r.is_failed(), "the path with a space should be read-only");
+}
+
+#[async_std::test]
+async fn test_access_path_prefix() {
+    for p in ["-/tmp", "+/tmp", "-+/tmp"] {
+        let r = RunSystem::new("/bin/true")
+            .read_only_paths([p])
+            .start()
+            .await;
+        assert!(matches!(r, Err(Error::InvalidPath(x)) if x == p));
+    }
+}
+
+#[async_std::test]
+async fn test_access_path_relative() {
+    let r = RunSystem::new("/bin/true")
+        .read_only_paths(["tmp"])
+        .start()
+        .await;
+    assert!(matches!(r, Err(Error::InvalidPath(x)) if x == "tmp"));
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "systemd_236")]
+
+use systemd_run::{Error, RunUser, SystemCallFilter};
+
+#[async_std::test]
+async fn test_system_call_filter_kill() {
+    let filter = SystemCallFilter::deny().syscall("uname");
+    let r = RunUser::new("/bin/uname")
+        .system_call_filter(filter)
+        .start()
+        .await
+        .expect("should be able to start /bin/uname")
+        .wait()
+        .await
+        .expect("should be able to get the status of the Run");
+    assert!(r.is_failed(), "uname should fail");
+    assert!(
+        r.is_killed_by_system_call_filter(),
+        "uname should be killed by SIGSYS"
+    );
+}
+
+#[async_std::test]
+async fn test_system_call_filter_error_number() {
+    let filter = SystemCallFilter::deny()
+        .syscall("uname")
+        .error_number(libc::EPERM);
+    let r = RunUser::new("/bin/uname")
+        .system_call_filter(filter)
+        .start()
+        .await
+        .expect("should be able to start /bin/uname")
+        .wait()
+        .await
+        .expect("should be able to get the status of the Run");
+    assert!(r.is_failed(), "uname should fail");
+    assert!(
+        r.exit_status().and_then(|x| x.code()).is_some(),
+        "uname should exit instead of being killed"
+    );
+    assert!(!r.is_killed_by_system_call_filter());
+}
+
+#[async_std::test]
+async fn test_system_call_filter_invalid() {
+    let filter = SystemCallFilter::allow()
+        .group("system-service")
+        .except("bad name");
+    let r = RunUser::new("/bin/true")
+        .system_call_filter(filter)
+        .start()
+        .await;
+    assert!(matches!(r, Err(Error::InvalidSystemCall(x)) if x == "bad name"));
+}
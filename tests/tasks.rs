@@ -0,0 +1,61 @@
+#![cfg(feature = "systemd_227")]
+use futures::stream::TryStreamExt;
+use std::time::Duration;
+use systemd_run::RunUser;
+
+#[async_std::test]
+async fn test_tasks_max() {
+    const E: &str = concat!(env!("OUT_DIR"), "/test-aux/waste-pid");
+    // Unlike limit_nproc, this does not count the other processes of the
+    // user.  Set runtime_max() because some implementations may dead lock
+    // when PID is exhausted.
+    let r = RunUser::new(E)
+        .tasks_max(16.try_into().unwrap())
+        .runtime_max(Duration::from_secs(1))
+        .collect_on_fail()
+        .start()
+        .await
+        .expect("should be able to start test waste-pid")
+        .wait()
+        .await
+        .expect("should be able to get the status of the Run");
+    assert!(r.is_failed(), "pid shouldn't be wasted with no penalty");
+}
+
+#[async_std::test]
+async fn test_tasks_peak() {
+    let run = RunUser::new("/bin/sh")
+        .args(["-c", "sleep 1 & sleep 1 & wait"])
+        .tasks_max(16.try_into().unwrap())
+        .start()
+        .await
+        .expect("should be able to start /bin/sh");
+    let _: Vec<_> = run
+        .monitor(Duration::from_millis(200))
+        .try_collect()
+        .await
+        .expect("should be able to sample the Run");
+    let r = run
+        .wait()
+        .await
+        .expect("should be able to get the status of the Run");
+    assert!(!r.is_failed(), "/bin/sh should run successfully");
+    assert_eq!(r.tasks_peak().ok(), Some(3), "sh and two sleeps");
+}
+
+#[async_std::test]
+async fn test_tasks_peak_without_sample() {
+    // The peak is only recorded by the samples, as the control group is
+    // removed once the service is stopped.
+    let r = RunUser::new("/bin/sh")
+        .args(["-c", "sleep 1 & sleep 1 & wait"])
+        .tasks_max(16.try_into().unwrap())
+        .start()
+        .await
+        .expect("should be able to start /bin/sh")
+        .wait()
+        .await
+        .expect("should be able to get the status of the Run");
+    assert!(!r.is_failed(), "/bin/sh should run successfully");
+    assert!(r.tasks_peak().is_err(), "no sample has been taken");
+}
@@ -3,7 +3,7 @@ use systemd_run::RunUser;
 
 #[async_std::test]
 async fn test_unpriv_private_users() {
-    const PATH: &'static str = concat!(env!("OUT_DIR"), "/test-aux/setuid");
+    const PATH: &str = concat!(env!("OUT_DIR"), "/test-aux/setuid");
     let r = RunUser::new(PATH)
         .private_users()
         .start()